@@ -1,8 +1,14 @@
 use core::num::NonZeroU64;
 
 use clap::{Parser, Subcommand};
-use imbibe_domain::Sha256;
+use imbibe_domain::{
+	Address, Sha256,
+	block::TimeBound,
+	filter::TxFilter,
+	page::{EvidenceCursor, HeightRange, Order, TxCursor},
+};
 use imbibe_querier::tarpc::QueryClient;
+use jiff::Timestamp;
 use tarpc::{client::Config, context, tokio_serde::formats::Json};
 
 #[derive(Parser)]
@@ -32,6 +38,14 @@ enum Command {
 		/// is a hex string SHA256 hash.
 		block_hash: String,
 	},
+	BlockByTime {
+		/// is the time to look the block up for. e.g. 2025-01-01T00:00:00Z
+		time: Timestamp,
+
+		/// picks the first block at or after the time, instead of the block current at the time.
+		#[arg(long)]
+		at_or_after: bool,
+	},
 	TxByHeightAndTxIdx {
 		/// must be a positive integer.
 		height: NonZeroU64,
@@ -43,6 +57,190 @@ enum Command {
 		/// is a hex string SHA256 hash.
 		tx_hash: String,
 	},
+	TxsByHeight {
+		/// must be a positive integer.
+		height: NonZeroU64,
+	},
+	IndexedHeight {
+		/// is the chain id of the indexed chain.
+		chain_id: String,
+	},
+	TxsByAddress {
+		/// is a bech32 or hex string account address.
+		address: String,
+
+		/// is the height of the last tx of the previous page.
+		#[arg(long, requires = "cursor_tx_idx")]
+		cursor_height: Option<NonZeroU64>,
+
+		/// is the index in block of the last tx of the previous page.
+		#[arg(long, requires = "cursor_height")]
+		cursor_tx_idx: Option<u64>,
+
+		/// is the maximum number of txs in the page.
+		#[arg(long, default_value = "10")]
+		limit: NonZeroU64,
+	},
+	Blocks {
+		/// is the lowest height of the blocks, inclusive.
+		#[arg(long)]
+		lo: Option<NonZeroU64>,
+
+		/// is the highest height of the blocks, inclusive.
+		#[arg(long)]
+		hi: Option<NonZeroU64>,
+
+		/// is the height of the last block of the previous page.
+		#[arg(long)]
+		cursor_height: Option<NonZeroU64>,
+
+		/// is either asc or desc.
+		#[arg(long, default_value = "desc", value_parser = parse_order)]
+		order: Order,
+
+		/// is the maximum number of blocks in the page.
+		#[arg(long, default_value = "10")]
+		limit: NonZeroU64,
+	},
+	Txs {
+		/// is the lowest height of the blocks of the txs, inclusive.
+		#[arg(long)]
+		lo: Option<NonZeroU64>,
+
+		/// is the highest height of the blocks of the txs, inclusive.
+		#[arg(long)]
+		hi: Option<NonZeroU64>,
+
+		/// is the height of the last tx of the previous page.
+		#[arg(long, requires = "cursor_tx_idx")]
+		cursor_height: Option<NonZeroU64>,
+
+		/// is the index in block of the last tx of the previous page.
+		#[arg(long, requires = "cursor_height")]
+		cursor_tx_idx: Option<u64>,
+
+		/// is either asc or desc.
+		#[arg(long, default_value = "desc", value_parser = parse_order)]
+		order: Order,
+
+		/// is the maximum number of txs in the page.
+		#[arg(long, default_value = "10")]
+		limit: NonZeroU64,
+	},
+	SearchTxs {
+		/// is the lowest height of the blocks of the txs, inclusive.
+		#[arg(long)]
+		lo: Option<NonZeroU64>,
+
+		/// is the highest height of the blocks of the txs, inclusive.
+		#[arg(long)]
+		hi: Option<NonZeroU64>,
+
+		/// is the earliest time of the blocks of the txs, inclusive. e.g. 2025-01-01T00:00:00Z
+		#[arg(long)]
+		time_from: Option<Timestamp>,
+
+		/// is the latest time of the blocks of the txs, inclusive. e.g. 2025-01-01T00:00:00Z
+		#[arg(long)]
+		time_to: Option<Timestamp>,
+
+		/// is the type url of any of the msgs of the txs. e.g. /cosmos.bank.v1beta1.MsgSend
+		#[arg(long)]
+		type_url: Option<String>,
+
+		/// is the result code of the txs.
+		#[arg(long)]
+		code: Option<u32>,
+
+		/// is true for txs with non zero result code, and false for txs with zero result code.
+		#[arg(long)]
+		failed: Option<bool>,
+
+		/// is the codespace of the result code of the txs.
+		#[arg(long)]
+		codespace: Option<String>,
+
+		/// is a substring of the memo of the txs.
+		#[arg(long)]
+		memo_contains: Option<String>,
+
+		/// is the height of the last tx of the previous page.
+		#[arg(long, requires = "cursor_tx_idx")]
+		cursor_height: Option<NonZeroU64>,
+
+		/// is the index in block of the last tx of the previous page.
+		#[arg(long, requires = "cursor_height")]
+		cursor_tx_idx: Option<u64>,
+
+		/// is either asc or desc.
+		#[arg(long, default_value = "desc", value_parser = parse_order)]
+		order: Order,
+
+		/// is the maximum number of txs in the page.
+		#[arg(long, default_value = "10")]
+		limit: NonZeroU64,
+	},
+	ValidatorSet {
+		/// must be a positive integer.
+		height: NonZeroU64,
+	},
+	BlockSigners {
+		/// must be a positive integer.
+		height: NonZeroU64,
+	},
+	MissedBlocks {
+		/// is a bech32 or hex string validator consensus address.
+		address: String,
+
+		/// is the lowest height of the blocks, inclusive.
+		#[arg(long)]
+		lo: Option<NonZeroU64>,
+
+		/// is the highest height of the blocks, inclusive.
+		#[arg(long)]
+		hi: Option<NonZeroU64>,
+
+		/// is the height of the last block of the previous page.
+		#[arg(long)]
+		cursor_height: Option<NonZeroU64>,
+
+		/// is either asc or desc.
+		#[arg(long, default_value = "desc", value_parser = parse_order)]
+		order: Order,
+
+		/// is the maximum number of blocks in the page.
+		#[arg(long, default_value = "10")]
+		limit: NonZeroU64,
+	},
+	Evidence {
+		/// is the lowest height of the blocks the evidence is committed in, inclusive.
+		#[arg(long)]
+		lo: Option<NonZeroU64>,
+
+		/// is the highest height of the blocks the evidence is committed in, inclusive.
+		#[arg(long)]
+		hi: Option<NonZeroU64>,
+
+		/// is a bech32 or hex string address of an offending validator.
+		#[arg(long)]
+		validator: Option<String>,
+
+		/// is the height of the last evidence of the previous page.
+		#[arg(long, requires = "cursor_evidence_idx")]
+		cursor_height: Option<NonZeroU64>,
+
+		/// is the index in block of the last evidence of the previous page.
+		#[arg(long, requires = "cursor_height")]
+		cursor_evidence_idx: Option<u64>,
+
+		/// is either asc or desc.
+		#[arg(long, default_value = "desc", value_parser = parse_order)]
+		order: Order,
+
+		/// is the maximum number of evidence in the page.
+		#[arg(long, default_value = "10")]
+		limit: NonZeroU64,
+	},
 }
 
 #[tokio::main]
@@ -62,12 +260,23 @@ async fn main() -> anyhow::Result<()> {
 				.inspect(|block| println!("block at height {height}:\n{block:#?}"))?;
 		},
 		Command::BlockByBlockHash { block_hash } => {
-			let hash = Sha256::new(const_hex::decode_to_array(&block_hash)?);
+			let hash = block_hash.parse::<Sha256>()?;
 			client
 				.block_by_block_hash(context::current(), hash)
 				.await?
 				.inspect(|block| println!("block at height {block_hash}:\n{block:#?}"))?;
 		},
+		Command::BlockByTime { time, at_or_after } => {
+			let bound = if at_or_after {
+				TimeBound::AtOrAfter
+			} else {
+				TimeBound::AtOrBefore
+			};
+			client
+				.block_by_time(context::current(), time, bound)
+				.await?
+				.inspect(|block| println!("block at time {time} ({bound:?}):\n{block:#?}"))?;
+		},
 		Command::TxByHeightAndTxIdx { height, tx_idx_in_block } => {
 			client
 				.tx_by_block_height_and_tx_idx_in_block(context::current(), height, tx_idx_in_block)
@@ -79,13 +288,179 @@ async fn main() -> anyhow::Result<()> {
 				})?;
 		},
 		Command::TxByTxHash { tx_hash } => {
-			let hash = Sha256::new(const_hex::decode_to_array(&tx_hash)?);
+			let hash = tx_hash.parse::<Sha256>()?;
 			client
 				.tx_by_tx_hash(context::current(), hash)
 				.await?
 				.inspect(|tx| println!("tx with tx hash {tx_hash}:\n{tx:#?}"))?;
 		},
+		Command::TxsByHeight { height } => {
+			client
+				.txs_by_block_height(context::current(), height)
+				.await?
+				.inspect(|txs| println!("txs at height {height}:\n{txs:#?}"))?;
+		},
+		Command::IndexedHeight { chain_id } => {
+			match client.indexed_height(context::current(), chain_id.clone()).await?? {
+				Some(height) => println!("chain {chain_id} is indexed upto height {height}"),
+				None => println!("chain {chain_id} has no indexed blocks"),
+			}
+		},
+		Command::TxsByAddress { address, cursor_height, cursor_tx_idx, limit } => {
+			let addr = address.parse::<Address>()?;
+			let cursor = cursor_height.zip(cursor_tx_idx).map(|(height, tx_idx_in_block)| {
+				TxCursor::builder().block_height(height).tx_idx_in_block(tx_idx_in_block).build()
+			});
+
+			client.txs_by_address(context::current(), addr, cursor, limit).await?.inspect(
+				|page| {
+					println!("txs of account {address}:\n{:#?}", page.items());
+					if let Some(cursor) = page.next_cursor() {
+						println!(
+							"next page: --cursor-height {} --cursor-tx-idx {}",
+							cursor.block_height(),
+							cursor.tx_idx_in_block()
+						);
+					}
+				},
+			)?;
+		},
+		Command::Blocks { lo, hi, cursor_height, order, limit } => {
+			let range = HeightRange::builder().maybe_lo(lo).maybe_hi(hi).build();
+
+			client.blocks(context::current(), range, cursor_height, order, limit).await?.inspect(
+				|page| {
+					println!("blocks:\n{:#?}", page.items());
+					if let Some(cursor) = page.next_cursor() {
+						println!("next page: --cursor-height {cursor}");
+					}
+				},
+			)?;
+		},
+		Command::Txs { lo, hi, cursor_height, cursor_tx_idx, order, limit } => {
+			let range = HeightRange::builder().maybe_lo(lo).maybe_hi(hi).build();
+			let cursor = cursor_height.zip(cursor_tx_idx).map(|(height, tx_idx_in_block)| {
+				TxCursor::builder().block_height(height).tx_idx_in_block(tx_idx_in_block).build()
+			});
+
+			client.txs(context::current(), range, cursor, order, limit).await?.inspect(|page| {
+				println!("txs:\n{:#?}", page.items());
+				if let Some(cursor) = page.next_cursor() {
+					println!(
+						"next page: --cursor-height {} --cursor-tx-idx {}",
+						cursor.block_height(),
+						cursor.tx_idx_in_block()
+					);
+				}
+			})?;
+		},
+		Command::SearchTxs {
+			lo,
+			hi,
+			time_from,
+			time_to,
+			type_url,
+			code,
+			failed,
+			codespace,
+			memo_contains,
+			cursor_height,
+			cursor_tx_idx,
+			order,
+			limit,
+		} => {
+			let filter = TxFilter::builder()
+				.heights(HeightRange::builder().maybe_lo(lo).maybe_hi(hi).build())
+				.maybe_time_from(time_from)
+				.maybe_time_to(time_to)
+				.maybe_type_url(type_url)
+				.maybe_code(code)
+				.maybe_failed(failed)
+				.maybe_codespace(codespace)
+				.maybe_memo_contains(memo_contains)
+				.build();
+			let cursor = cursor_height.zip(cursor_tx_idx).map(|(height, tx_idx_in_block)| {
+				TxCursor::builder().block_height(height).tx_idx_in_block(tx_idx_in_block).build()
+			});
+
+			client.search_txs(context::current(), filter, cursor, order, limit).await?.inspect(
+				|page| {
+					println!("txs:\n{:#?}", page.items());
+					if let Some(cursor) = page.next_cursor() {
+						println!(
+							"next page: --cursor-height {} --cursor-tx-idx {}",
+							cursor.block_height(),
+							cursor.tx_idx_in_block()
+						);
+					}
+				},
+			)?;
+		},
+		Command::ValidatorSet { height } => {
+			client.validator_set(context::current(), height).await?.inspect(|validator_set| {
+				println!("validator set of block at height {height}:\n{validator_set:#?}")
+			})?;
+		},
+		Command::BlockSigners { height } => {
+			client.block_signers(context::current(), height).await?.inspect(|signers| {
+				println!("signers of block at height {height}:\n{signers:#?}")
+			})?;
+		},
+		Command::MissedBlocks { address, lo, hi, cursor_height, order, limit } => {
+			let addr = address.parse::<Address>()?;
+			let range = HeightRange::builder().maybe_lo(lo).maybe_hi(hi).build();
+
+			client
+				.missed_blocks(context::current(), addr, range, cursor_height, order, limit)
+				.await?
+				.inspect(|page| {
+					println!("blocks missed by validator {address}:\n{:?}", page.items());
+					if let Some(cursor) = page.next_cursor() {
+						println!("next page: --cursor-height {cursor}");
+					}
+				})?;
+		},
+		Command::Evidence {
+			lo,
+			hi,
+			validator,
+			cursor_height,
+			cursor_evidence_idx,
+			order,
+			limit,
+		} => {
+			let range = HeightRange::builder().maybe_lo(lo).maybe_hi(hi).build();
+			let validator = validator.as_deref().map(str::parse::<Address>).transpose()?;
+			let cursor = cursor_height.zip(cursor_evidence_idx).map(|(height, evidence_idx)| {
+				EvidenceCursor::builder()
+					.block_height(height)
+					.evidence_idx_in_block(evidence_idx)
+					.build()
+			});
+
+			client
+				.evidence(context::current(), range, validator, cursor, order, limit)
+				.await?
+				.inspect(|page| {
+					println!("evidence:\n{:#?}", page.items());
+					if let Some(cursor) = page.next_cursor() {
+						println!(
+							"next page: --cursor-height {} --cursor-evidence-idx {}",
+							cursor.block_height(),
+							cursor.evidence_idx_in_block()
+						);
+					}
+				})?;
+		},
 	};
 
 	Ok(())
 }
+
+fn parse_order(s: &str) -> Result<Order, String> {
+	match s {
+		"asc" => Ok(Order::Asc),
+		"desc" => Ok(Order::Desc),
+		_ => Err(format!("order must be either asc or desc, got {s}")),
+	}
+}
@@ -0,0 +1,233 @@
+use core::{
+	fmt::{self, Display, Formatter},
+	str::FromStr,
+};
+
+use bon::Builder;
+use cosmrs::{AccountId, tendermint::account::Id};
+use sha3::{Digest, Keccak256};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Address(Id);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum AddressKind {
+	Account,
+	ValidatorOperator,
+	ValidatorConsensus,
+}
+
+#[derive(Debug, Clone, Builder)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Bech32Prefixes {
+	#[builder(into)]
+	account: String,
+
+	#[builder(into)]
+	validator_operator: String,
+
+	#[builder(into)]
+	validator_consensus: String,
+}
+
+#[non_exhaustive]
+#[derive(Debug, thiserror::Error)]
+pub enum AddressError {
+	#[error("bech32 error: {0}")]
+	Bech32(String),
+
+	#[error("prefix error: expected prefix {expected}, got {got}")]
+	Prefix { expected: String, got: String },
+
+	#[error("length error: address must be exactly {} bytes long", Address::LEN)]
+	Length,
+
+	#[error("hex error: {0}")]
+	Hex(#[from] const_hex::FromHexError),
+}
+
+impl Address {
+	pub const LEN: usize = 20;
+
+	pub fn new(bz: [u8; Self::LEN]) -> Self {
+		Self(Id::new(bz))
+	}
+
+	pub fn as_bytes(&self) -> &[u8] {
+		self.0.as_bytes()
+	}
+
+	pub fn to_bech32(&self, prefix: &str) -> Result<String, AddressError> {
+		AccountId::new(prefix, self.as_bytes())
+			.map(String::from)
+			.map_err(|e| AddressError::Bech32(e.to_string()))
+	}
+
+	// decodes a bech32 address of any prefix, returning the prefix along with the address
+	pub fn from_bech32(s: &str) -> Result<(String, Self), AddressError> {
+		let account_id = s.parse::<AccountId>().map_err(|e| AddressError::Bech32(e.to_string()))?;
+		let address = Self::try_from(account_id.to_bytes().as_slice())?;
+
+		Ok((account_id.prefix().to_owned(), address))
+	}
+
+	// decodes a hex address, with or without `0x` prefix, if the string is one at all
+	fn from_hex(s: &str) -> Option<Result<Self, AddressError>> {
+		let hex = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")).unwrap_or(s);
+
+		(hex.len() == Self::LEN * 2 && hex.bytes().all(|b| b.is_ascii_hexdigit()))
+			.then(|| const_hex::decode_to_array(hex).map(Self::new).map_err(From::from))
+	}
+
+	// renders the address as an EIP-55 checksummed hex string, as used by ethsecp256k1 chains
+	pub fn to_evm_hex(&self) -> String {
+		let hex = const_hex::encode(self.as_bytes());
+		let hash = Keccak256::digest(hex.as_bytes());
+
+		let checksummed = hex
+			.char_indices()
+			.map(|(i, c)| {
+				let nibble = (hash[i / 2] >> if i % 2 == 0 { 4 } else { 0 }) & 0xf;
+				if nibble >= 8 {
+					c.to_ascii_uppercase()
+				} else {
+					c
+				}
+			})
+			.collect::<String>();
+
+		format!("0x{checksummed}")
+	}
+}
+
+impl From<Id> for Address {
+	fn from(id: Id) -> Self {
+		Self(id)
+	}
+}
+
+impl TryFrom<&[u8]> for Address {
+	type Error = AddressError;
+
+	fn try_from(bz: &[u8]) -> Result<Self, Self::Error> {
+		bz.try_into().map(Self::new).map_err(|_| AddressError::Length)
+	}
+}
+
+// renders the address as an upper case hex string, the same as tendermint does
+impl Display for Address {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		Display::fmt(&self.0, f)
+	}
+}
+
+// parses a hex address, with or without `0x` prefix, or a bech32 address of any prefix
+impl FromStr for Address {
+	type Err = AddressError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		Self::from_hex(s).unwrap_or_else(|| Self::from_bech32(s).map(|(_, address)| address))
+	}
+}
+
+impl Bech32Prefixes {
+	// derives the validator prefixes from the account prefix, as per the cosmos sdk convention
+	pub fn from_account_prefix(prefix: &str) -> Self {
+		Self {
+			account: prefix.to_owned(),
+			validator_operator: format!("{prefix}valoper"),
+			validator_consensus: format!("{prefix}valcons"),
+		}
+	}
+
+	pub fn get(&self, kind: AddressKind) -> &str {
+		match kind {
+			AddressKind::Account => &self.account,
+			AddressKind::ValidatorOperator => &self.validator_operator,
+			AddressKind::ValidatorConsensus => &self.validator_consensus,
+		}
+	}
+
+	pub fn encode(&self, address: &Address, kind: AddressKind) -> Result<String, AddressError> {
+		address.to_bech32(self.get(kind))
+	}
+
+	pub fn decode(&self, s: &str, kind: AddressKind) -> Result<Address, AddressError> {
+		let (prefix, address) = Address::from_bech32(s)?;
+
+		if prefix != self.get(kind) {
+			return Err(AddressError::Prefix { expected: self.get(kind).to_owned(), got: prefix });
+		}
+
+		Ok(address)
+	}
+
+	// parses a hex address, or a bech32 address of the prefix of the kind
+	pub fn parse(&self, s: &str, kind: AddressKind) -> Result<Address, AddressError> {
+		Address::from_hex(s).unwrap_or_else(|| self.decode(s, kind))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn evm_hex_is_eip55_checksummed() {
+		// test vectors of EIP-55
+		for checksummed in [
+			"0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+			"0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+			"0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+			"0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+		] {
+			let address = checksummed.parse::<Address>().unwrap();
+
+			assert_eq!(address.to_evm_hex(), checksummed);
+			assert_eq!(
+				checksummed.to_lowercase().parse::<Address>().unwrap(),
+				address
+			);
+		}
+	}
+
+	#[test]
+	fn bech32_round_trips() {
+		let prefixes = Bech32Prefixes::from_account_prefix("cosmos");
+		let address = Address::new(core::array::from_fn(|i| i as u8));
+
+		for (kind, prefix) in [
+			(AddressKind::Account, "cosmos1"),
+			(AddressKind::ValidatorOperator, "cosmosvaloper1"),
+			(AddressKind::ValidatorConsensus, "cosmosvalcons1"),
+		] {
+			let bech32 = prefixes.encode(&address, kind).unwrap();
+
+			assert!(bech32.starts_with(prefix));
+			assert_eq!(prefixes.decode(&bech32, kind).unwrap(), address);
+			assert_eq!(bech32.parse::<Address>().unwrap(), address);
+		}
+	}
+
+	#[test]
+	fn bech32_of_other_prefix_is_rejected() {
+		let address = Address::new([7; Address::LEN]);
+		let bech32 = address.to_bech32("osmo").unwrap();
+
+		assert!(matches!(
+			Bech32Prefixes::from_account_prefix("cosmos").parse(&bech32, AddressKind::Account),
+			Err(AddressError::Prefix { .. })
+		));
+		assert_eq!(
+			Bech32Prefixes::from_account_prefix("cosmos")
+				.parse(&address.to_string(), AddressKind::Account)
+				.unwrap(),
+			address
+		);
+	}
+}
@@ -1,4 +1,7 @@
-use core::fmt::{self, Debug, Formatter};
+use core::{
+	fmt::{self, Debug, Display, Formatter},
+	str::FromStr,
+};
 
 use bon::Builder;
 use bytes::Bytes;
@@ -43,6 +46,16 @@ pub struct AppHash(Vec<u8>);
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct BlockData<T>(Vec<NonEmptyBz<T>>);
 
+// picks the block for a time that falls between the times of two blocks; `AtOrBefore` yields the
+// block that was current at the time
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum TimeBound {
+	#[default]
+	AtOrBefore,
+	AtOrAfter,
+}
+
 impl Block {
 	pub fn header(&self) -> &Header {
 		&self.header
@@ -140,3 +153,17 @@ impl Debug for AppHash {
 		write!(f, "AppHash({})", const_hex::encode(self.get()))
 	}
 }
+
+impl Display for AppHash {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.write_str(&const_hex::encode_upper(self.get()))
+	}
+}
+
+impl FromStr for AppHash {
+	type Err = const_hex::FromHexError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		const_hex::decode(s).map(Self::new)
+	}
+}
@@ -0,0 +1,64 @@
+use core::num::NonZeroU64;
+
+use bon::Builder;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Builder)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Event {
+	block_height: NonZeroU64,
+	event_idx_in_block: u64,
+	tx_idx_in_block: Option<u64>,
+	kind: String,
+	attributes: Vec<EventAttribute>,
+}
+
+#[derive(Debug, Clone, Builder)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct EventAttribute {
+	key: String,
+	value: String,
+	index: bool,
+}
+
+impl Event {
+	pub fn block_height(&self) -> NonZeroU64 {
+		self.block_height
+	}
+
+	pub fn event_idx_in_block(&self) -> u64 {
+		self.event_idx_in_block
+	}
+
+	pub fn tx_idx_in_block(&self) -> Option<u64> {
+		self.tx_idx_in_block
+	}
+
+	pub fn kind(&self) -> &str {
+		&self.kind
+	}
+
+	pub fn attributes(&self) -> &[EventAttribute] {
+		&self.attributes
+	}
+
+	pub fn is_block_event(&self) -> bool {
+		self.tx_idx_in_block.is_none()
+	}
+}
+
+impl EventAttribute {
+	pub fn key(&self) -> &str {
+		&self.key
+	}
+
+	pub fn value(&self) -> &str {
+		&self.value
+	}
+
+	pub fn index(&self) -> bool {
+		self.index
+	}
+}
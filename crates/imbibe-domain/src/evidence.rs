@@ -0,0 +1,69 @@
+use core::num::NonZeroU64;
+
+use bon::Builder;
+use jiff::Timestamp;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::Address;
+
+// evidence of a misbehaviour of the validators, committed in the block at the block height
+#[derive(Debug, Clone, Builder)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Evidence {
+	block_height: NonZeroU64,
+	evidence_idx_in_block: u64,
+	kind: EvidenceKind,
+
+	// height and time of the misbehaviour
+	height: NonZeroU64,
+	time: Timestamp,
+
+	// voting power of the validator set at the height of the misbehaviour
+	total_voting_power: u64,
+
+	// the offending validators
+	validators: Vec<Address>,
+}
+
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum EvidenceKind {
+	// a validator signing conflicting votes at the same height and round
+	DuplicateVote { validator_power: u64 },
+
+	// validators signing a conflicting block to deceive a light client, which trusted the
+	// validator set at the common height
+	LightClientAttack { common_height: NonZeroU64 },
+}
+
+impl Evidence {
+	pub fn block_height(&self) -> NonZeroU64 {
+		self.block_height
+	}
+
+	pub fn evidence_idx_in_block(&self) -> u64 {
+		self.evidence_idx_in_block
+	}
+
+	pub fn kind(&self) -> &EvidenceKind {
+		&self.kind
+	}
+
+	pub fn height(&self) -> NonZeroU64 {
+		self.height
+	}
+
+	pub fn time(&self) -> &Timestamp {
+		&self.time
+	}
+
+	pub fn total_voting_power(&self) -> u64 {
+		self.total_voting_power
+	}
+
+	pub fn validators(&self) -> &[Address] {
+		&self.validators
+	}
+}
@@ -0,0 +1,103 @@
+use bon::Builder;
+use jiff::Timestamp;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{Address, page::HeightRange, tx::Tx};
+
+// criteria of a tx search, the criteria that are set must all match
+#[derive(Debug, Clone, Default, Builder)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TxFilter {
+	#[builder(default)]
+	heights: HeightRange,
+
+	// inclusive range of the time of the block of the tx
+	time_from: Option<Timestamp>,
+	time_to: Option<Timestamp>,
+
+	// matches if any of the msgs of the tx has the type url
+	#[builder(into)]
+	type_url: Option<String>,
+
+	code: Option<u32>,
+
+	// matches txs with a non zero code if set to true, and zero code if set to false
+	failed: Option<bool>,
+
+	#[builder(into)]
+	codespace: Option<String>,
+
+	#[builder(into)]
+	memo_contains: Option<String>,
+}
+
+// criteria of the live txs pushed to a subscriber, the criteria that are set must all match
+#[derive(Debug, Clone, Default, Builder)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TxSubscriptionFilter {
+	// matches if any of the msgs of the tx has the type url
+	#[builder(into)]
+	type_url: Option<String>,
+
+	// matches if the account is involved in the tx
+	address: Option<Address>,
+}
+
+impl TxFilter {
+	pub fn heights(&self) -> HeightRange {
+		self.heights
+	}
+
+	pub fn time_from(&self) -> Option<Timestamp> {
+		self.time_from
+	}
+
+	pub fn time_to(&self) -> Option<Timestamp> {
+		self.time_to
+	}
+
+	pub fn type_url(&self) -> Option<&str> {
+		self.type_url.as_deref()
+	}
+
+	pub fn code(&self) -> Option<u32> {
+		self.code
+	}
+
+	pub fn failed(&self) -> Option<bool> {
+		self.failed
+	}
+
+	pub fn codespace(&self) -> Option<&str> {
+		self.codespace.as_deref()
+	}
+
+	pub fn memo_contains(&self) -> Option<&str> {
+		self.memo_contains.as_deref()
+	}
+}
+
+impl TxSubscriptionFilter {
+	pub fn type_url(&self) -> Option<&str> {
+		self.type_url.as_deref()
+	}
+
+	pub fn address(&self) -> Option<&Address> {
+		self.address.as_ref()
+	}
+
+	pub fn matches(&self, tx: &Tx) -> bool {
+		self.type_url
+			.as_deref()
+			.is_none_or(|type_url| tx.msgs().get().iter().any(|msg| msg.type_url == type_url))
+			&& self.address.as_ref().is_none_or(|address| tx.accounts().contains(address))
+	}
+}
+
+impl From<HeightRange> for TxFilter {
+	fn from(heights: HeightRange) -> Self {
+		Self { heights, ..Default::default() }
+	}
+}
@@ -0,0 +1,260 @@
+// opt-in serde helpers for byte strings, which serialize them as hex strings in the human readable
+// formats such as JSON, and as plain bytes in the binary formats, e.g. with
+// `#[serde(with = "imbibe_domain::hex::lower")]`; when deserializing from the human readable formats,
+// hex strings of either case are accepted along with the byte arrays the types derive otherwise
+use core::fmt::{self, Formatter};
+
+use serde::{Deserializer, Serializer, de};
+
+use crate::{Sha256, block::AppHash};
+
+// the byte strings the helpers can serialize and deserialize
+pub trait HexBytes: Sized {
+	fn hex_bytes(&self) -> &[u8];
+
+	// yields `None` if the bytes are not of a valid length
+	fn from_hex_bytes(bz: Vec<u8>) -> Option<Self>;
+}
+
+// upper case, as per the CometBFT JSON conventions
+pub mod upper {
+	use serde::{Deserializer, Serializer};
+
+	use super::HexBytes;
+
+	pub fn serialize<T, S>(bz: &T, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		T: HexBytes,
+		S: Serializer,
+	{
+		super::serialize(bz.hex_bytes(), serializer, |bz| const_hex::encode_upper(bz))
+	}
+
+	pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+	where
+		T: HexBytes,
+		D: Deserializer<'de>,
+	{
+		super::deserialize(deserializer)
+	}
+}
+
+pub mod lower {
+	use serde::{Deserializer, Serializer};
+
+	use super::HexBytes;
+
+	pub fn serialize<T, S>(bz: &T, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		T: HexBytes,
+		S: Serializer,
+	{
+		super::serialize(bz.hex_bytes(), serializer, |bz| const_hex::encode(bz))
+	}
+
+	pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+	where
+		T: HexBytes,
+		D: Deserializer<'de>,
+	{
+		super::deserialize(deserializer)
+	}
+}
+
+fn serialize<S>(bz: &[u8], serializer: S, encode: fn(&[u8]) -> String) -> Result<S::Ok, S::Error>
+where
+	S: Serializer,
+{
+	if serializer.is_human_readable() {
+		serializer.serialize_str(&encode(bz))
+	} else {
+		serializer.serialize_bytes(bz)
+	}
+}
+
+fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+	T: HexBytes,
+	D: Deserializer<'de>,
+{
+	let bz = if deserializer.is_human_readable() {
+		deserializer.deserialize_any(BytesVisitor)?
+	} else {
+		deserializer.deserialize_byte_buf(BytesVisitor)?
+	};
+
+	let len = bz.len();
+	T::from_hex_bytes(bz)
+		.ok_or_else(|| de::Error::invalid_length(len, &"a byte string of a valid length"))
+}
+
+struct BytesVisitor;
+
+impl<'de> de::Visitor<'de> for BytesVisitor {
+	type Value = Vec<u8>;
+
+	fn expecting(&self, f: &mut Formatter) -> fmt::Result {
+		f.write_str("a hex string or a byte string")
+	}
+
+	fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+	where
+		E: de::Error,
+	{
+		const_hex::decode(v).map_err(de::Error::custom)
+	}
+
+	fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+	where
+		E: de::Error,
+	{
+		Ok(v.to_vec())
+	}
+
+	fn visit_byte_buf<E>(self, v: Vec<u8>) -> Result<Self::Value, E>
+	where
+		E: de::Error,
+	{
+		Ok(v)
+	}
+
+	fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+	where
+		A: de::SeqAccess<'de>,
+	{
+		let mut bz = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+		while let Some(b) = seq.next_element()? {
+			bz.push(b);
+		}
+
+		Ok(bz)
+	}
+}
+
+impl HexBytes for Vec<u8> {
+	fn hex_bytes(&self) -> &[u8] {
+		self
+	}
+
+	fn from_hex_bytes(bz: Vec<u8>) -> Option<Self> {
+		Some(bz)
+	}
+}
+
+impl<const N: usize> HexBytes for [u8; N] {
+	fn hex_bytes(&self) -> &[u8] {
+		self
+	}
+
+	fn from_hex_bytes(bz: Vec<u8>) -> Option<Self> {
+		bz.try_into().ok()
+	}
+}
+
+impl HexBytes for Sha256 {
+	fn hex_bytes(&self) -> &[u8] {
+		self.get()
+	}
+
+	fn from_hex_bytes(bz: Vec<u8>) -> Option<Self> {
+		bz.try_into().ok().map(Self::new)
+	}
+}
+
+impl HexBytes for AppHash {
+	fn hex_bytes(&self) -> &[u8] {
+		self.get()
+	}
+
+	fn from_hex_bytes(bz: Vec<u8>) -> Option<Self> {
+		Some(Self::new(bz))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use serde::{Deserialize, Serialize};
+	use serde_json::json;
+
+	use crate::{Sha256, block::AppHash};
+
+	#[derive(Serialize, Deserialize)]
+	struct Hashes {
+		sha256: Sha256,
+		app_hash: AppHash,
+	}
+
+	#[derive(Serialize, Deserialize)]
+	struct HexHashes {
+		#[serde(with = "super::upper")]
+		sha256: Sha256,
+
+		#[serde(with = "super::lower")]
+		app_hash: AppHash,
+	}
+
+	const SHA256: [u8; Sha256::LEN] = [0xab; Sha256::LEN];
+	const APP_HASH: [u8; 4] = [0xcd; 4];
+
+	fn assert_hashes(sha256: &Sha256, app_hash: &AppHash) {
+		assert_eq!(sha256.get(), &SHA256);
+		assert_eq!(app_hash.get(), APP_HASH);
+	}
+
+	#[test]
+	fn derived_round_trip() {
+		let hashes =
+			Hashes { sha256: Sha256::new(SHA256), app_hash: AppHash::new(APP_HASH.to_vec()) };
+
+		let json = serde_json::to_value(&hashes).unwrap();
+		assert_eq!(json, json!({ "sha256": SHA256, "app_hash": APP_HASH }));
+
+		let hashes = serde_json::from_value::<Hashes>(json).unwrap();
+		assert_hashes(&hashes.sha256, &hashes.app_hash);
+
+		let bz = bincode::serialize(&hashes).unwrap();
+		let hashes = bincode::deserialize::<Hashes>(&bz).unwrap();
+		assert_hashes(&hashes.sha256, &hashes.app_hash);
+	}
+
+	#[test]
+	fn hex_round_trip() {
+		let hashes =
+			HexHashes { sha256: Sha256::new(SHA256), app_hash: AppHash::new(APP_HASH.to_vec()) };
+
+		let json = serde_json::to_value(&hashes).unwrap();
+		assert_eq!(
+			json,
+			json!({ "sha256": "AB".repeat(Sha256::LEN), "app_hash": "cdcdcdcd" })
+		);
+
+		let hashes = serde_json::from_value::<HexHashes>(json).unwrap();
+		assert_hashes(&hashes.sha256, &hashes.app_hash);
+
+		// plain bytes in the binary formats
+		let bz = bincode::serialize(&hashes).unwrap();
+		assert_eq!(bz.len(), 8 + Sha256::LEN + 8 + APP_HASH.len());
+
+		let hashes = bincode::deserialize::<HexHashes>(&bz).unwrap();
+		assert_hashes(&hashes.sha256, &hashes.app_hash);
+	}
+
+	#[test]
+	fn hex_deserializes_either_case_and_derived() {
+		let hashes = serde_json::from_value::<HexHashes>(
+			json!({ "sha256": "ab".repeat(Sha256::LEN), "app_hash": "CDCDCDCD" }),
+		)
+		.unwrap();
+		assert_hashes(&hashes.sha256, &hashes.app_hash);
+
+		let hashes =
+			serde_json::from_value::<HexHashes>(json!({ "sha256": SHA256, "app_hash": APP_HASH }))
+				.unwrap();
+		assert_hashes(&hashes.sha256, &hashes.app_hash);
+
+		assert!(
+			serde_json::from_value::<HexHashes>(json!({ "sha256": "abcd", "app_hash": "" }))
+				.is_err()
+		);
+	}
+}
@@ -1,21 +1,30 @@
 pub mod block;
+pub mod event;
+pub mod evidence;
+pub mod filter;
+pub mod page;
 pub mod tx;
+pub mod validator;
 
-use core::fmt::{Debug, Formatter, Result};
+#[cfg(feature = "serde")]
+pub mod hex;
+
+mod address;
+
+pub use self::address::{Address, AddressError, AddressKind, Bech32Prefixes};
+
+use core::{
+	fmt::{Debug, Display, Formatter, Result},
+	str::FromStr,
+};
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-use cosmrs::tendermint::account::Id;
-
 #[derive(Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Sha256([u8; Self::LEN]);
 
-#[derive(Debug, Clone)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-pub struct Address(Id);
-
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct NonEmptyBz<T>(T);
@@ -51,15 +60,18 @@ impl Debug for Sha256 {
 	}
 }
 
-impl Address {
-	const ACCOUNT_LEN: usize = 20;
-
-	pub fn new(bz: [u8; Self::ACCOUNT_LEN]) -> Self {
-		Self(Id::new(bz))
+// renders the hash as an upper case hex string, as per the CometBFT conventions
+impl Display for Sha256 {
+	fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+		f.write_str(&const_hex::encode_upper(self.get()))
 	}
+}
+
+impl FromStr for Sha256 {
+	type Err = const_hex::FromHexError;
 
-	pub fn as_bytes(&self) -> &[u8] {
-		self.0.as_bytes()
+	fn from_str(s: &str) -> core::result::Result<Self, Self::Err> {
+		const_hex::decode_to_array(s).map(Self::new)
 	}
 }
 
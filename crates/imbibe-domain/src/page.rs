@@ -0,0 +1,161 @@
+use core::num::NonZeroU64;
+
+use bon::Builder;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{evidence::Evidence, tx::Tx};
+
+#[derive(Debug, Clone, Builder)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Page<T, C> {
+	items: Vec<T>,
+	next_cursor: Option<C>,
+}
+
+// position of a tx in the chain, used as a keyset cursor for paginating txs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Builder)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TxCursor {
+	block_height: NonZeroU64,
+	tx_idx_in_block: u64,
+}
+
+// position of an evidence in the chain, used as a keyset cursor for paginating evidence
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Builder)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct EvidenceCursor {
+	block_height: NonZeroU64,
+	evidence_idx_in_block: u64,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Order {
+	Asc,
+	#[default]
+	Desc,
+}
+
+// inclusive range of block heights, unbounded on the ends that are not set
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Builder)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct HeightRange {
+	lo: Option<NonZeroU64>,
+	hi: Option<NonZeroU64>,
+}
+
+impl<T, C> Page<T, C> {
+	pub fn items(&self) -> &[T] {
+		&self.items
+	}
+
+	pub fn next_cursor(&self) -> Option<&C> {
+		self.next_cursor.as_ref()
+	}
+
+	pub fn into_items(self) -> Vec<T> {
+		self.items
+	}
+}
+
+impl HeightRange {
+	pub fn lo(&self) -> Option<NonZeroU64> {
+		self.lo
+	}
+
+	pub fn hi(&self) -> Option<NonZeroU64> {
+		self.hi
+	}
+
+	pub fn single(height: NonZeroU64) -> Self {
+		Self { lo: Some(height), hi: Some(height) }
+	}
+}
+
+impl TxCursor {
+	pub fn block_height(&self) -> NonZeroU64 {
+		self.block_height
+	}
+
+	pub fn tx_idx_in_block(&self) -> u64 {
+		self.tx_idx_in_block
+	}
+}
+
+impl From<&Tx> for TxCursor {
+	fn from(tx: &Tx) -> Self {
+		Self { block_height: tx.block_height(), tx_idx_in_block: tx.tx_idx_in_block() }
+	}
+}
+
+impl EvidenceCursor {
+	pub fn block_height(&self) -> NonZeroU64 {
+		self.block_height
+	}
+
+	pub fn evidence_idx_in_block(&self) -> u64 {
+		self.evidence_idx_in_block
+	}
+}
+
+impl From<&Evidence> for EvidenceCursor {
+	fn from(evidence: &Evidence) -> Self {
+		Self {
+			block_height: evidence.block_height(),
+			evidence_idx_in_block: evidence.evidence_idx_in_block(),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use core::num::NonZeroU64;
+
+	use super::TxCursor;
+
+	fn cursor(block_height: u64, tx_idx_in_block: u64) -> TxCursor {
+		TxCursor::builder()
+			.block_height(NonZeroU64::new(block_height).unwrap())
+			.tx_idx_in_block(tx_idx_in_block)
+			.build()
+	}
+
+	// the cursors order as the keyset of the txs, by height and then by index in the block
+	#[test]
+	fn tx_cursor_order() {
+		let mut cursors = [cursor(2, 0), cursor(1, 5), cursor(2, 3), cursor(1, 0)];
+		cursors.sort();
+
+		assert_eq!(
+			cursors,
+			[cursor(1, 0), cursor(1, 5), cursor(2, 0), cursor(2, 3)]
+		);
+	}
+
+	#[cfg(feature = "serde")]
+	#[test]
+	fn tx_cursor_round_trip() {
+		use serde_json::json;
+
+		let json = serde_json::to_value(cursor(7, 2)).unwrap();
+		assert_eq!(json, json!({ "block_height": 7, "tx_idx_in_block": 2 }));
+		assert_eq!(
+			serde_json::from_value::<TxCursor>(json).unwrap(),
+			cursor(7, 2)
+		);
+
+		let bz = bincode::serialize(&cursor(7, 2)).unwrap();
+		assert_eq!(bincode::deserialize::<TxCursor>(&bz).unwrap(), cursor(7, 2));
+
+		// the height of a cursor is positive
+		assert!(
+			serde_json::from_value::<TxCursor>(json!({ "block_height": 0, "tx_idx_in_block": 2 }))
+				.is_err()
+		);
+		assert!(
+			bincode::deserialize::<TxCursor>(&bincode::serialize(&(0u64, 2u64)).unwrap()).is_err()
+		);
+	}
+}
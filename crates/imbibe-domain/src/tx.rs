@@ -39,8 +39,15 @@ pub struct Tx<DBZ = Bytes, TBZ = Bytes> {
 	fees: Option<Fees>,
 	payer: Address,
 	granter: Option<Address>,
+
+	// unique accounts involved in the tx: the fee payer, the fee granter, the signers and the
+	// msg signers
+	accounts: Vec<Address>,
+
 	code: Code,
 	codespace: Option<Codespace>,
+	log: Option<Log>,
+	info: Option<Info>,
 	gas_limit: u64,
 	gas_wanted: u64,
 	gas_used: u64,
@@ -56,13 +63,27 @@ pub struct Memo(String);
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Codespace(String);
 
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Log(String);
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Info(String);
+
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Fees(Vec<Coin>);
 
 #[derive(Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-pub struct Msgs(Vec<Any>);
+pub struct Msgs {
+	msgs: Vec<Any>,
+
+	// canonical proto3 JSON of each of the msgs, if it could be decoded
+	#[cfg_attr(feature = "serde", serde(default))]
+	json: Vec<Option<String>>,
+}
 
 impl Tx {
 	pub fn block_height(&self) -> NonZeroU64 {
@@ -109,6 +130,10 @@ impl Tx {
 		self.granter.as_ref()
 	}
 
+	pub fn accounts(&self) -> &[Address] {
+		&self.accounts
+	}
+
 	pub fn code(&self) -> Code {
 		self.code
 	}
@@ -117,6 +142,14 @@ impl Tx {
 		self.codespace.as_ref()
 	}
 
+	pub fn log(&self) -> Option<&Log> {
+		self.log.as_ref()
+	}
+
+	pub fn info(&self) -> Option<&Info> {
+		self.info.as_ref()
+	}
+
 	pub fn gas_limit(&self) -> u64 {
 		self.gas_limit
 	}
@@ -150,6 +183,18 @@ impl Codespace {
 	}
 }
 
+impl Log {
+	pub fn new(log: String) -> Option<Self> {
+		(!log.is_empty()).then_some(log).map(Self)
+	}
+}
+
+impl Info {
+	pub fn new(info: String) -> Option<Self> {
+		(!info.is_empty()).then_some(info).map(Self)
+	}
+}
+
 impl Fees {
 	pub fn new(fees: Vec<Coin>) -> Option<Self> {
 		(!fees.is_empty()).then_some(Self(fees))
@@ -162,11 +207,21 @@ impl Fees {
 
 impl Msgs {
 	pub fn new(msgs: Vec<Any>) -> Option<Self> {
-		(!msgs.is_empty()).then_some(Self(msgs))
+		let json = vec![None; msgs.len()];
+		Self::with_json(msgs, json)
+	}
+
+	// the json must be given for each of the msgs, in the same order as the msgs
+	pub fn with_json(msgs: Vec<Any>, json: Vec<Option<String>>) -> Option<Self> {
+		(!msgs.is_empty() && msgs.len() == json.len()).then_some(Self { msgs, json })
 	}
 
 	pub fn get(&self) -> &[Any] {
-		&self.0
+		&self.msgs
+	}
+
+	pub fn json(&self) -> &[Option<String>] {
+		&self.json
 	}
 }
 
@@ -194,10 +249,34 @@ impl From<Codespace> for String {
 	}
 }
 
+impl AsRef<str> for Log {
+	fn as_ref(&self) -> &str {
+		&self.0
+	}
+}
+
+impl From<Log> for String {
+	fn from(log: Log) -> Self {
+		log.0
+	}
+}
+
+impl AsRef<str> for Info {
+	fn as_ref(&self) -> &str {
+		&self.0
+	}
+}
+
+impl From<Info> for String {
+	fn from(info: Info) -> Self {
+		info.0
+	}
+}
+
 impl fmt::Debug for Msgs {
 	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
 		let msgs: Vec<_> = self
-			.0
+			.msgs
 			.iter()
 			.map(|any| {
 				format!(
@@ -207,7 +286,7 @@ impl fmt::Debug for Msgs {
 				)
 			})
 			.collect();
-		f.debug_struct("Msgs").field("msgs", &msgs).finish()
+		f.debug_struct("Msgs").field("msgs", &msgs).field("json", &self.json).finish()
 	}
 }
 
@@ -0,0 +1,128 @@
+use core::num::NonZeroU64;
+
+use bon::Builder;
+use cosmrs::tendermint::PublicKey;
+use jiff::Timestamp;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::{Address, Sha256};
+
+// validators of the set hashing to the validators hash, in the canonical order of the set, which
+// the signatures of a commit are in
+#[derive(Debug, Clone, Builder)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ValidatorSet {
+	validators_hash: Sha256,
+	validators: Vec<Validator>,
+}
+
+#[derive(Debug, Clone, Builder)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Validator {
+	address: Address,
+	pub_key: PublicKey,
+	voting_power: u64,
+}
+
+// vote of a validator in the last commit included in a block, i.e. the commit of the block below
+#[derive(Debug, Clone, Builder)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CommitSignature {
+	block_height: NonZeroU64,
+	validator_idx: u64,
+	vote: Vote,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Vote {
+	Absent,
+	Commit {
+		validator_address: Address,
+		time: Timestamp,
+	},
+	Nil {
+		validator_address: Address,
+		time: Timestamp,
+	},
+}
+
+// a member of the validator set of a block along with its vote for the block
+#[derive(Debug, Clone, Builder)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BlockSigner {
+	validator: Validator,
+	vote: Vote,
+}
+
+impl ValidatorSet {
+	pub fn validators_hash(&self) -> &Sha256 {
+		&self.validators_hash
+	}
+
+	pub fn validators(&self) -> &[Validator] {
+		&self.validators
+	}
+}
+
+impl Validator {
+	pub fn address(&self) -> &Address {
+		&self.address
+	}
+
+	pub fn pub_key(&self) -> &PublicKey {
+		&self.pub_key
+	}
+
+	pub fn voting_power(&self) -> u64 {
+		self.voting_power
+	}
+}
+
+impl CommitSignature {
+	pub fn block_height(&self) -> NonZeroU64 {
+		self.block_height
+	}
+
+	pub fn validator_idx(&self) -> u64 {
+		self.validator_idx
+	}
+
+	pub fn vote(&self) -> &Vote {
+		&self.vote
+	}
+}
+
+impl Vote {
+	pub fn validator_address(&self) -> Option<&Address> {
+		match self {
+			Self::Absent => None,
+			Self::Commit { validator_address, .. } | Self::Nil { validator_address, .. } => {
+				Some(validator_address)
+			},
+		}
+	}
+
+	pub fn time(&self) -> Option<&Timestamp> {
+		match self {
+			Self::Absent => None,
+			Self::Commit { time, .. } | Self::Nil { time, .. } => Some(time),
+		}
+	}
+
+	pub fn is_commit(&self) -> bool {
+		matches!(self, Self::Commit { .. })
+	}
+}
+
+impl BlockSigner {
+	pub fn validator(&self) -> &Validator {
+		&self.validator
+	}
+
+	pub fn vote(&self) -> &Vote {
+		&self.vote
+	}
+}
@@ -1,11 +1,21 @@
-use core::num::{NonZeroU64, NonZeroUsize};
+use core::{
+	num::{NonZeroU32, NonZeroU64, NonZeroUsize},
+	slice,
+};
 
 use cosmrs::tendermint::block::Height;
 use futures::{StreamExt, TryStreamExt};
-use imbibe_persistence::{pool::DbPool, store};
+use imbibe_domain::block::Header;
+use imbibe_persistence::{
+	pool::DbPool,
+	store::{self, WriteMode},
+};
 use tendermint_rpc::{Client, WebSocketClient};
 
-use crate::error::{IndexerError, Result};
+use crate::{
+	BlockWithTxs, RetryPolicy,
+	error::{IndexerError, Result},
+};
 
 pub struct BackfillIndexer {
 	pool: DbPool,
@@ -14,6 +24,7 @@ pub struct BackfillIndexer {
 	workers: NonZeroUsize,
 	lo: NonZeroU64,
 	hi: NonZeroU64,
+	retry_policy: RetryPolicy,
 }
 
 #[bon::bon]
@@ -26,56 +37,292 @@ impl BackfillIndexer {
 		workers: NonZeroUsize,
 		lo: NonZeroU64,
 		hi: NonZeroU64,
+		#[builder(default)] retry_policy: RetryPolicy,
 	) -> Result<Self> {
-		if lo >= hi {
+		if lo > hi {
 			return Err(IndexerError::Other(
-				"hi must be strictly greater than lo".into(),
+				"hi must be greater than or equal to lo".into(),
 			));
 		}
 
-		Ok(BackfillIndexer { pool, client, batch, workers, lo, hi })
+		Ok(BackfillIndexer { pool, client, batch, workers, lo, hi, retry_policy })
 	}
 }
 
 impl BackfillIndexer {
 	#[tracing::instrument(skip_all)]
 	pub async fn start(self) -> Result<()> {
-		tracing::info!("backfilling blocks from {} upto {}", self.lo, self.hi);
+		let status = self.client.status().await?;
+		let chain_id = status.node_info.network.to_string();
+		let earliest = NonZeroU64::new(status.sync_info.earliest_block_height.value())
+			.unwrap_or(NonZeroU64::MIN);
 
-		store::fetch_missing_block_heights(&mut self.pool.get().await?, self.lo, self.hi)
+		super::metrics::record_chain_head_height(status.sync_info.latest_block_height.value());
+
+		let Some(lo) = self.reachable_lo(&chain_id, earliest).await? else {
+			tracing::info!("no reachable blocks to backfill upto {}", self.hi);
+			return Ok(());
+		};
+
+		tracing::info!("backfilling blocks from {} upto {}", lo, self.hi);
+
+		store::fetch_missing_block_heights(&mut self.pool.get().await?, lo, self.hi)
 			.await?
 			.inspect_ok(|h| tracing::info!("backfilling block {h}"))
 			.inspect_err(|e| tracing::error!("store error: {e}"))
-			.map_ok(NonZeroU64::get)
-			.map_ok(Height::try_from)
-			.map_ok(|res| res.map_err(|_| IndexerError::RpcHeight))
-			.map(|height| height?)
-			.map_ok(|height| (height, self.client.clone()))
-			.and_then(async |(height, client)| {
-				let block_resp = client.block(height).await?;
-				let block_results_resp = client.block_results(height).await?;
+			.map_err(IndexerError::from)
+			.try_filter_map(async |height| Ok(self.fetch_block(&chain_id, height).await))
+			.try_chunks(self.batch.get())
+			.map_err(|e| IndexerError::Other(e.into()))
+			// boxed as the compiler otherwise fails to prove that the future of `start` is `Send`
+			.boxed()
+			.try_for_each_concurrent(self.workers.get(), async |blocks| {
+				let blocks = self.index_validator_sets(&chain_id, blocks).await;
+				if !blocks.is_empty() {
+					self.save_blocks(&chain_id, blocks).await;
+				}
+
+				Ok(())
+			})
+			.await?;
+
+		tracing::info!("finished backfilling blocks from {} upto {}", lo, self.hi);
+
+		Ok(())
+	}
+
+	async fn reachable_lo(
+		&self,
+		chain_id: &str,
+		earliest: NonZeroU64,
+	) -> Result<Option<NonZeroU64>> {
+		// pruned nodes and nodes of chains upgraded via genesis export do not serve the blocks
+		// below their earliest block
+		if self.lo >= earliest {
+			return Ok(Some(self.lo));
+		}
+
+		let unreachable_hi = earliest.get() - 1;
+
+		tracing::warn!(
+			"blocks from {} upto {} are unreachable as the earliest block available on the node is {}, skipping them",
+			self.lo,
+			unreachable_hi.min(self.hi.get()),
+			earliest,
+		);
+
+		let mut conn = self.pool.get().await?;
+		store::raise_start_height(&mut conn, chain_id, earliest.min(self.hi.saturating_add(1)))
+			.await?;
+		super::advance_indexed_height(&mut conn, chain_id).await?;
+
+		Ok((earliest <= self.hi).then_some(earliest))
+	}
+
+	// fetches and processes the block, dead lettering it if it keeps failing
+	async fn fetch_block(&self, chain_id: &str, height: NonZeroU64) -> Option<BlockWithTxs> {
+		let res = self
+			.retry_policy
+			.retry(|| async {
+				let height = Height::try_from(height.get()).map_err(|_| IndexerError::RpcHeight)?;
+				let block_resp = self.client.block(height).await?;
+				let block_results_resp = self.client.block_results(height).await?;
 
 				let header = block_resp.block.header;
 				let hash = block_resp.block_id.hash;
 				let data = block_resp.block.data;
 				let exec_tx_results = block_results_resp.txs_results.unwrap_or_default();
+				let block_events = block_results_resp
+					.begin_block_events
+					.unwrap_or_default()
+					.into_iter()
+					.chain(block_results_resp.end_block_events.unwrap_or_default())
+					.chain(block_results_resp.finalize_block_events)
+					.collect();
+
+				let last_commit = block_resp.block.last_commit;
+				let evidence = block_resp.block.evidence;
+
+				let block = super::process_block(
+					header,
+					hash,
+					data,
+					exec_tx_results,
+					block_events,
+					last_commit,
+					evidence,
+				)?;
 
-				super::process_block(header, hash, data, exec_tx_results)
+				Ok(block)
 			})
-			.try_chunks(self.batch.get())
-			.map_err(|e| IndexerError::Other(e.into()))
-			.and_then(async |blocks| Ok((self.pool.get().await?, blocks)))
-			.try_for_each_concurrent(self.workers.get(), async |(mut conn, tbrs)| {
-				store::save_blocks_with_txs(&mut conn, &tbrs).await.map_err(From::from)
+			.await;
+
+		match res {
+			Ok(block) => Some(block),
+			Err((attempts, e)) => {
+				self.dead_letter(chain_id, height.get(), attempts, &e).await;
+				None
+			},
+		}
+	}
+
+	// indexes the validator sets of the blocks which are not stored yet, checking the whole batch
+	// at once rather than block by block; the blocks whose validator set keeps failing to be
+	// indexed are dead lettered, and the rest are returned to be saved
+	async fn index_validator_sets(
+		&self,
+		chain_id: &str,
+		blocks: Vec<BlockWithTxs>,
+	) -> Vec<BlockWithTxs> {
+		// the first block of each validator set, which the set is fetched at
+		let mut headers = Vec::<&Header>::new();
+		for (block, ..) in &blocks {
+			let header = block.header();
+			if headers.iter().all(|h| h.validators_hash().get() != header.validators_hash().get()) {
+				headers.push(header);
+			}
+		}
+
+		let validators_hashes =
+			headers.iter().map(|header| header.validators_hash().clone()).collect::<Vec<_>>();
+		let res = self
+			.retry_policy
+			.retry(|| async {
+				let mut conn = self.pool.get().await?;
+				store::fetch_stored_validators_hashes(&mut conn, &validators_hashes)
+					.await
+					.map_err(From::from)
 			})
-			.await?;
+			.await;
 
-		tracing::info!(
-			"finished backfilling blocks from {} upto {}",
-			self.lo,
-			self.hi
-		);
+		// each validator set is checked on its own when it is indexed, hence a failed check of the
+		// batch only costs the round trips it would have saved
+		let stored = res.unwrap_or_else(|(_, e)| {
+			tracing::warn!("failed to check the stored validator sets of the batch: {e}");
+			vec![]
+		});
 
-		Ok(())
+		let mut failed = vec![];
+		for header in headers {
+			if stored.iter().any(|hash| hash.get() == header.validators_hash().get()) {
+				continue;
+			}
+
+			let res = self
+				.retry_policy
+				.retry(|| super::index_validator_set(&self.client, &self.pool, header))
+				.await;
+
+			if let Err(e) = res {
+				failed.push((header.validators_hash().clone(), e));
+			}
+		}
+
+		if failed.is_empty() {
+			return blocks;
+		}
+
+		let mut indexed = Vec::with_capacity(blocks.len());
+		for block in blocks {
+			let header = block.0.header();
+			match failed.iter().find(|(hash, _)| hash.get() == header.validators_hash().get()) {
+				Some((_, (attempts, e))) => {
+					self.dead_letter(chain_id, header.height(), *attempts, e).await
+				},
+				None => indexed.push(block),
+			}
+		}
+
+		indexed
+	}
+
+	// saves the blocks as a batch, falling back to saving them one by one so that a single
+	// failing block does not hold back the rest of the batch
+	async fn save_blocks(&self, chain_id: &str, blocks: Vec<BlockWithTxs>) {
+		let saved_heights = match self.try_save_blocks(&blocks).await {
+			Ok(()) => blocks.iter().map(|(block, ..)| block.header().height()).collect(),
+			Err((attempts, e)) if blocks.len() == 1 => {
+				let height = blocks[0].0.header().height();
+				self.dead_letter(chain_id, height, attempts, &e).await;
+				vec![]
+			},
+			Err((_, e)) => {
+				tracing::warn!(
+					"failed to save batch of {} blocks: {e}, saving them one by one",
+					blocks.len()
+				);
+
+				let mut saved_heights = vec![];
+				for block in blocks {
+					let height = block.0.header().height();
+					match self.try_save_blocks(slice::from_ref(&block)).await {
+						Ok(()) => saved_heights.push(height),
+						Err((attempts, e)) => {
+							self.dead_letter(chain_id, height, attempts, &e).await
+						},
+					}
+				}
+
+				saved_heights
+			},
+		};
+
+		if saved_heights.is_empty() {
+			return;
+		}
+
+		let res = self
+			.retry_policy
+			.retry(|| async {
+				let mut conn = self.pool.get().await?;
+				store::remove_failed_blocks(&mut conn, chain_id, &saved_heights).await?;
+				super::advance_indexed_height(&mut conn, chain_id).await
+			})
+			.await;
+
+		if let Err((_, e)) = res {
+			tracing::error!("failed to advance indexed height: {e}");
+		}
+	}
+
+	async fn try_save_blocks(
+		&self,
+		blocks: &[BlockWithTxs],
+	) -> Result<(), (NonZeroU32, IndexerError)> {
+		self.retry_policy
+			.retry(|| async {
+				let mut conn = self.pool.get().await?;
+				store::save_blocks_with_txs(&mut conn, blocks, WriteMode::Idempotent).await?;
+				Ok(())
+			})
+			.await
+			.inspect(|_| {
+				let txs = blocks.iter().map(|(_, txs, ..)| txs.len()).sum();
+				super::metrics::record_indexed_blocks(blocks.len(), txs);
+			})
+	}
+
+	async fn dead_letter(
+		&self,
+		chain_id: &str,
+		height: u64,
+		attempts: NonZeroU32,
+		error: &IndexerError,
+	) {
+		tracing::error!("giving up on block {height} after {attempts} attempt(s): {error}");
+
+		let error = error.to_string();
+		let res = self
+			.retry_policy
+			.retry(|| async {
+				let mut conn = self.pool.get().await?;
+				store::save_failed_block(&mut conn, chain_id, height, attempts, &error).await?;
+				Ok(())
+			})
+			.await;
+
+		if let Err((_, e)) = res {
+			tracing::error!("failed to record failed block {height}: {e}");
+		}
 	}
 }
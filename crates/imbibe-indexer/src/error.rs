@@ -1,3 +1,4 @@
+use imbibe_domain::Sha256;
 use imbibe_persistence::{pool::PoolError, store::StoreError};
 
 pub type Result<T, E = IndexerError> = core::result::Result<T, E>;
@@ -26,6 +27,9 @@ pub enum IndexerError {
 	#[error("validator hash error: validator hash must be present")]
 	ValidatorHash,
 
+	#[error("validator set error: {0}")]
+	ValidatorSet(String),
+
 	#[error("next validators hash error: next validators hash must be present")]
 	NextValidatorsHash,
 
@@ -59,6 +63,25 @@ pub enum IndexerError {
 	)]
 	TxsInBlock,
 
+	#[error("events error: number of events must not exceed {}", u64::MAX)]
+	Events,
+
+	#[error(
+		"evidence error: number of evidence in single block must not exceed {}",
+		u64::MAX
+	)]
+	Evidence,
+
+	#[error(
+		"fork error: block {height} is stored with hash {stored}, but the node's block above it \
+		 chains onto {expected}"
+	)]
+	Fork {
+		height: u64,
+		stored: Sha256,
+		expected: Sha256,
+	},
+
 	#[error("store error: {0}")]
 	Store(#[from] StoreError),
 
@@ -74,3 +97,45 @@ pub enum IndexerError {
 	#[error("other error: {0}")]
 	Other(Box<dyn std::error::Error + Send + Sync>),
 }
+
+impl IndexerError {
+	// the kind of the error, by which the errors are counted in the metrics
+	pub fn kind(&self) -> &'static str {
+		match self {
+			Self::Rpc(_) => "rpc",
+			Self::BlockData(_) => "block_data",
+			Self::Timestamp(_) => "timestamp",
+			Self::Height => "height",
+			Self::Gas => "gas",
+			Self::BlockHash => "block_hash",
+			Self::ValidatorHash => "validator_hash",
+			Self::ValidatorSet(_) => "validator_set",
+			Self::NextValidatorsHash => "next_validators_hash",
+			Self::ConsensusHash => "consensus_hash",
+			Self::TxDecodeError => "tx_decode",
+			Self::TxDataDecodeError => "tx_data_decode",
+			Self::Bech32Address(_) => "bech32_address",
+			Self::Address => "address",
+			Self::Signer(_) => "signer",
+			Self::TxMsgsMissing => "tx_msgs_missing",
+			Self::UnsupportedPublicKey => "unsupported_public_key",
+			Self::TxsInBlock => "txs_in_block",
+			Self::Events => "events",
+			Self::Evidence => "evidence",
+			Self::Fork { .. } => "fork",
+			Self::Store(_) => "store",
+			Self::DbPool(_) => "db_pool",
+			Self::RpcHeight => "rpc_height",
+			Self::Other(_) => "other",
+		}
+	}
+
+	// errors that may go away on their own, such as network or database hiccups, as opposed to
+	// the errors in the block data itself
+	pub fn is_transient(&self) -> bool {
+		matches!(
+			self,
+			Self::Rpc(_) | Self::DbPool(_) | Self::Store(StoreError::Db(_))
+		)
+	}
+}
@@ -1,31 +1,62 @@
 pub mod error;
+pub mod metrics;
 
 mod backfill;
 mod live;
+mod retry;
 
-pub use tendermint_rpc::WebSocketClient;
+pub use tendermint_rpc::{Client, SubscriptionClient, WebSocketClient};
 
-pub use self::{backfill::BackfillIndexer, live::LiveIndexer};
+pub use self::{
+	backfill::BackfillIndexer,
+	live::{ForkPolicy, LiveIndexer},
+	retry::RetryPolicy,
+};
 
-use core::num::NonZeroU64;
+use core::{mem, num::NonZeroU64};
 
 use base64::{Engine, prelude::BASE64_STANDARD};
 use bytes::Bytes;
 use cosmrs::{
-	AccountId,
-	tendermint::{Hash, abci::types::ExecTxResult, block::header::Header as TendermintBlockHeader},
+	AccountId, Any,
+	tendermint::{
+		Hash, PublicKey as TendermintPublicKey,
+		abci::{Event as AbciEvent, EventAttribute as AbciEventAttribute, types::ExecTxResult},
+		account::Id,
+		block::{
+			Commit, CommitSig, Height as TendermintHeight, header::Header as TendermintBlockHeader,
+		},
+		evidence::{Evidence as TendermintEvidence, List as EvidenceList},
+		validator::{Info as ValidatorInfo, Set},
+	},
 	tx::SignerPublicKey,
 };
 use imbibe_domain::{
 	Address, NonEmptyBz, Sha256,
 	block::{AppHash, Block, BlockData, Header},
-	tx::{Codespace, Fees, Memo, Msgs, Tx},
+	event::{Event, EventAttribute},
+	evidence::{Evidence, EvidenceKind},
+	tx::{Codespace, Fees, Info, Log, Memo, Msgs, Tx},
+	validator::{CommitSignature, Validator, ValidatorSet, Vote},
+};
+use imbibe_persistence::{
+	pool::{DbConn, DbPool},
+	store,
 };
 use jiff::Timestamp;
 use sha2::Digest;
+use tendermint_rpc::Paging;
 
 use self::error::{IndexerError, Result};
 
+type BlockWithTxs = (
+	Block,
+	Vec<Tx>,
+	Vec<Event>,
+	Vec<CommitSignature>,
+	Vec<Evidence>,
+);
+
 #[allow(private_bounds)]
 pub trait Indexer: Sealed {
 	type Output;
@@ -35,12 +66,50 @@ pub trait Indexer: Sealed {
 
 trait Sealed {}
 
+async fn advance_indexed_height(conn: &mut DbConn, chain_id: &str) -> Result<()> {
+	if let Some(height) = store::advance_indexed_height(conn, chain_id).await? {
+		tracing::info!("indexed contiguously upto block {height}");
+		metrics::record_indexed_height(height.get());
+	}
+
+	Ok(())
+}
+
+// fetches and saves the validator set of the block unless it is already stored, which is only
+// the case when the validator set changes
+async fn index_validator_set<C>(client: &C, pool: &DbPool, header: &Header) -> Result<()>
+where
+	C: Client + Sync,
+{
+	if store::validator_set_exists(&mut pool.get().await?, header.validators_hash()).await? {
+		return Ok(());
+	}
+
+	let height =
+		TendermintHeight::try_from(header.height()).map_err(|_| IndexerError::RpcHeight)?;
+	let validators = client.validators(height, Paging::All).await?.validators;
+	let validator_set = make_validator_set(header.validators_hash(), validators)?;
+
+	store::save_validator_set(&mut pool.get().await?, &validator_set).await?;
+	tracing::info!(
+		"indexed validator set {} of {} validators at block {}",
+		validator_set.validators_hash(),
+		validator_set.validators().len(),
+		header.height()
+	);
+
+	Ok(())
+}
+
 fn process_block(
 	header: TendermintBlockHeader,
 	hash: Hash,
 	data: Vec<Vec<u8>>,
 	exec_tx_results: Vec<ExecTxResult>,
-) -> Result<(Block, Vec<Tx>)> {
+	block_events: Vec<AbciEvent>,
+	last_commit: Option<Commit>,
+	evidence: EvidenceList,
+) -> Result<BlockWithTxs> {
 	if data.len() != exec_tx_results.len() {
 		return Err(IndexerError::BlockData(format!(
 			"mismatch in number of tx included in block {}",
@@ -57,8 +126,18 @@ fn process_block(
 		.and_then(BlockData::new)
 		.ok_or(IndexerError::BlockData("invalid block data".into()))?;
 
-	let (total_gas_used, tx_results) =
-		process_block_txs(height, data.get().iter().cloned().zip(exec_tx_results))?;
+	// block level events come first so that their indices do not depend on the txs of the block
+	let mut events = vec![];
+	push_events(height, None, block_events, &mut events)?;
+
+	let (total_gas_used, tx_results) = process_block_txs(
+		height,
+		data.get().iter().cloned().zip(exec_tx_results),
+		&mut events,
+	)?;
+
+	let commit_signatures = make_commit_signatures(height, last_commit)?;
+	let evidence = make_evidence(height, evidence)?;
 
 	let block = Block::builder()
 		.header(make_header(header)?)
@@ -67,10 +146,14 @@ fn process_block(
 		.gas_used(total_gas_used)
 		.build();
 
-	Ok((block, tx_results))
+	Ok((block, tx_results, events, commit_signatures, evidence))
 }
 
-fn process_block_txs<I>(height: NonZeroU64, tbr: I) -> Result<(u64, Vec<Tx>)>
+fn process_block_txs<I>(
+	height: NonZeroU64,
+	tbr: I,
+	events: &mut Vec<Event>,
+) -> Result<(u64, Vec<Tx>)>
 where
 	I: Iterator<Item = (NonEmptyBz<Bytes>, ExecTxResult)>,
 {
@@ -78,10 +161,10 @@ where
 	let txrs = tbr
 		.into_iter()
 		.enumerate()
-		.map(|(idx, (bz, res))| {
-			idx.try_into()
-				.map_err(|_| IndexerError::TxsInBlock)
-				.and_then(|idx| make_tx(height, idx, bz, res))
+		.map(|(idx, (bz, mut res))| {
+			let idx = idx.try_into().map_err(|_| IndexerError::TxsInBlock)?;
+			push_events(height, Some(idx), mem::take(&mut res.events), events)?;
+			make_tx(height, idx, bz, res)
 		})
 		.map(|tx| {
 			let tx = tx?;
@@ -93,6 +176,39 @@ where
 	Ok((total_gas_used, txrs))
 }
 
+fn push_events<I>(
+	block_height: NonZeroU64,
+	tx_idx_in_block: Option<u64>,
+	abci_events: I,
+	events: &mut Vec<Event>,
+) -> Result<()>
+where
+	I: IntoIterator<Item = AbciEvent>,
+{
+	for abci_event in abci_events {
+		let event = Event::builder()
+			.block_height(block_height)
+			.event_idx_in_block(events.len().try_into().map_err(|_| IndexerError::Events)?)
+			.maybe_tx_idx_in_block(tx_idx_in_block)
+			.kind(abci_event.kind)
+			.attributes(abci_event.attributes.iter().map(make_event_attribute).collect())
+			.build();
+
+		events.push(event);
+	}
+
+	Ok(())
+}
+
+fn make_event_attribute(attribute: &AbciEventAttribute) -> EventAttribute {
+	// attributes prior to cometbft v0.37 may hold arbitrary bytes
+	EventAttribute::builder()
+		.key(String::from_utf8_lossy(attribute.key_bytes()).into_owned())
+		.value(String::from_utf8_lossy(attribute.value_bytes()).into_owned())
+		.index(attribute.index())
+		.build()
+}
+
 fn make_header(tm_header: TendermintBlockHeader) -> Result<Header> {
 	let header = Header::builder()
 		.chain_id(tm_header.chain_id.into())
@@ -123,6 +239,126 @@ fn make_header(tm_header: TendermintBlockHeader) -> Result<Header> {
 	Ok(header)
 }
 
+// orders the validators canonically, as the signatures of a commit are, and verifies that they
+// hash to the validators hash of the block
+fn make_validator_set(
+	validators_hash: &Sha256,
+	validators: Vec<ValidatorInfo>,
+) -> Result<ValidatorSet> {
+	let total_voting_power =
+		validators.iter().try_fold(0u64, |total, v| total.checked_add(v.power()));
+	if total_voting_power.is_none_or(|total| total > Set::MAX_TOTAL_VOTING_POWER) {
+		return Err(IndexerError::ValidatorSet(format!(
+			"total voting power must not exceed {}",
+			Set::MAX_TOTAL_VOTING_POWER
+		)));
+	}
+
+	let set = Set::without_proposer(validators);
+	let hash = make_sha256(set.hash()).ok_or(IndexerError::ValidatorHash)?;
+	if hash.get() != validators_hash.get() {
+		return Err(IndexerError::ValidatorSet(format!(
+			"validators hash to {hash}, expected {validators_hash}"
+		)));
+	}
+
+	let validators = set
+		.validators()
+		.iter()
+		.map(|v| {
+			Validator::builder()
+				.address(v.address.into())
+				.pub_key(v.pub_key)
+				.voting_power(v.power())
+				.build()
+		})
+		.collect();
+
+	Ok(ValidatorSet::builder().validators_hash(hash).validators(validators).build())
+}
+
+fn make_commit_signatures(
+	block_height: NonZeroU64,
+	last_commit: Option<Commit>,
+) -> Result<Vec<CommitSignature>> {
+	last_commit
+		.into_iter()
+		.flat_map(|commit| commit.signatures)
+		.enumerate()
+		.map(|(idx, signature)| {
+			let vote = match signature {
+				CommitSig::BlockIdFlagAbsent => Vote::Absent,
+				CommitSig::BlockIdFlagCommit { validator_address, timestamp, .. } => Vote::Commit {
+					validator_address: validator_address.into(),
+					time: Timestamp::from_nanosecond(timestamp.unix_timestamp_nanos())?,
+				},
+				CommitSig::BlockIdFlagNil { validator_address, timestamp, .. } => Vote::Nil {
+					validator_address: validator_address.into(),
+					time: Timestamp::from_nanosecond(timestamp.unix_timestamp_nanos())?,
+				},
+			};
+
+			let signature = CommitSignature::builder()
+				.block_height(block_height)
+				.validator_idx(idx.try_into().map_err(|_| {
+					IndexerError::ValidatorSet(
+						"number of validators must not exceed u64::MAX".into(),
+					)
+				})?)
+				.vote(vote)
+				.build();
+
+			Ok(signature)
+		})
+		.collect()
+}
+
+fn make_evidence(block_height: NonZeroU64, evidence: EvidenceList) -> Result<Vec<Evidence>> {
+	evidence
+		.into_vec()
+		.into_iter()
+		.enumerate()
+		.map(|(idx, evidence)| {
+			let (kind, height, time, total_voting_power, validators) = match evidence {
+				TendermintEvidence::DuplicateVote(evidence) => (
+					EvidenceKind::DuplicateVote {
+						validator_power: evidence.validator_power.value(),
+					},
+					evidence.vote_a.height.value(),
+					evidence.timestamp,
+					evidence.total_voting_power.value(),
+					vec![evidence.vote_a.validator_address.into()],
+				),
+				TendermintEvidence::LightClientAttack(evidence) => (
+					EvidenceKind::LightClientAttack {
+						common_height: evidence
+							.common_height
+							.value()
+							.try_into()
+							.map_err(|_| IndexerError::Height)?,
+					},
+					evidence.conflicting_block.signed_header.header.height.value(),
+					evidence.timestamp,
+					evidence.total_voting_power.value(),
+					evidence.byzantine_validators.into_iter().map(|v| v.address.into()).collect(),
+				),
+			};
+
+			let evidence = Evidence::builder()
+				.block_height(block_height)
+				.evidence_idx_in_block(idx.try_into().map_err(|_| IndexerError::Evidence)?)
+				.kind(kind)
+				.height(height.try_into().map_err(|_| IndexerError::Height)?)
+				.time(Timestamp::from_nanosecond(time.unix_timestamp_nanos())?)
+				.total_voting_power(total_voting_power)
+				.validators(validators)
+				.build();
+
+			Ok(evidence)
+		})
+		.collect()
+}
+
 fn make_sha256(hash: Hash) -> Option<Sha256> {
 	match hash {
 		Hash::Sha256(h) => Some(Sha256::new(h)),
@@ -171,29 +407,39 @@ fn make_tx(
 		},
 	};
 
+	let granter = cosm_tx
+		.auth_info
+		.fee
+		.granter
+		.map(|acc_id| acc_id.to_bytes())
+		.as_deref()
+		.map(|bz| bz.try_into().map(Address::new).map_err(|_| IndexerError::Address))
+		.transpose()?;
+
+	let accounts = tx_accounts(
+		&payer,
+		granter.as_ref(),
+		cosm_tx.auth_info.signer_infos.iter().flat_map(|si| si.public_key.as_ref()),
+		&cosm_tx.body.messages,
+	);
+
 	let tx = Tx::builder()
 		.block_height(block_height)
 		.tx_idx_in_block(tx_idx_in_block)
 		.tx_hash(sha2::Sha256::digest(&tx_bz).into())
-		.msgs(Msgs::new(cosm_tx.body.messages).ok_or(IndexerError::TxMsgsMissing)?)
+		.msgs(msgs_with_json(cosm_tx.body.messages).ok_or(IndexerError::TxMsgsMissing)?)
 		.maybe_memo(Memo::new(cosm_tx.body.memo))
 		.maybe_timeout_height(cosm_tx.body.timeout_height.value().try_into().ok())
 		.signatures(cosm_tx.signatures)
 		.signers(cosm_tx.auth_info.signer_infos.into_iter().flat_map(|si| si.public_key).collect())
 		.maybe_fees(Fees::new(cosm_tx.auth_info.fee.amount))
 		.payer(payer)
-		.maybe_granter(
-			cosm_tx
-				.auth_info
-				.fee
-				.granter
-				.map(|acc_id| acc_id.to_bytes())
-				.as_deref()
-				.map(|bz| bz.try_into().map(Address::new).map_err(|_| IndexerError::Address))
-				.transpose()?,
-		)
+		.maybe_granter(granter)
+		.accounts(accounts)
 		.code(exec_tx_result.code)
 		.maybe_codespace(Codespace::new(exec_tx_result.codespace))
+		.maybe_log(Log::new(exec_tx_result.log))
+		.maybe_info(Info::new(exec_tx_result.info))
 		.gas_limit(cosm_tx.auth_info.fee.gas_limit)
 		.gas_wanted(exec_tx_result.gas_wanted.try_into().map_err(|_| IndexerError::Gas)?)
 		.gas_used(exec_tx_result.gas_used.try_into().map_err(|_| IndexerError::Gas)?)
@@ -210,15 +456,68 @@ fn make_tx(
 	Ok(tx)
 }
 
+// attaches the canonical proto3 JSON of the msgs, leaving out the msgs that fail to get decoded
+// as the msgs are stored as is regardless
+fn msgs_with_json(msgs: Vec<Any>) -> Option<Msgs> {
+	#[cfg(not(feature = "msg-json"))]
+	return Msgs::new(msgs);
+
+	#[cfg(feature = "msg-json")]
+	{
+		let json = msgs
+			.iter()
+			.map(|msg| {
+				imbibe_protos::any_to_json(msg)
+					.inspect_err(|e| tracing::warn!("failed to decode msg into json: {e}"))
+					.ok()
+					.map(|json| json.to_string())
+			})
+			.collect();
+
+		Msgs::with_json(msgs, json)
+	}
+}
+
+// collects the unique accounts involved in a tx, skipping the signers whose address can not be
+// derived, such as multisig signers, and the msg signers that are not 20 bytes long
+fn tx_accounts<'a, I>(
+	payer: &Address,
+	granter: Option<&Address>,
+	signers: I,
+	msgs: &[Any],
+) -> Vec<Address>
+where
+	I: Iterator<Item = &'a SignerPublicKey>,
+{
+	let msg_signers = msgs
+		.iter()
+		.filter_map(|msg| imbibe_protos::unique_signers_from_any_msg(msg).ok())
+		.flatten()
+		.filter_map(|s| s.parse::<AccountId>().ok())
+		.filter_map(|acc_id| acc_id.to_bytes().as_slice().try_into().ok())
+		.map(Address::new);
+
+	let mut accounts = [payer.clone()]
+		.into_iter()
+		.chain(granter.cloned())
+		.chain(signers.filter_map(|signer| signer_address(signer).ok()))
+		.chain(msg_signers)
+		.collect::<Vec<_>>();
+
+	accounts.sort_unstable();
+	accounts.dedup();
+
+	accounts
+}
+
 fn signer_address(signer: &SignerPublicKey) -> Result<Address> {
 	match signer {
-		SignerPublicKey::Single(pk) => pk
-			.account_id("foo")
-			.map_err(|_| IndexerError::UnsupportedPublicKey)?
-			.to_bytes()
-			.try_into()
-			.map(Address::new)
-			.map_err(|_| IndexerError::Address),
+		// the address is derived from the key alone, the bech32 prefix is only a rendering detail;
+		// ed25519 keys are rejected, as cosmos sdk chains do not accept them as tx signers
+		SignerPublicKey::Single(pk) => match TendermintPublicKey::from(*pk) {
+			pk @ TendermintPublicKey::Secp256k1(_) => Ok(Id::from(pk).into()),
+			_ => Err(IndexerError::UnsupportedPublicKey),
+		},
 
 		#[cfg_attr(not(feature = "ethsecp256k1"), allow(unused_variables))]
 		SignerPublicKey::Any(any) => {
@@ -1,82 +1,416 @@
-use core::num::NonZeroU64;
+use core::{
+	num::{NonZeroU64, NonZeroUsize},
+	time::Duration,
+};
+use std::collections::HashSet;
 
-use bon::Builder;
-use futures::StreamExt;
-use imbibe_persistence::{pool::DbPool, store};
-use tendermint_rpc::{SubscriptionClient, WebSocketClient, event::EventData, query::EventType};
-use tokio::sync::oneshot;
+use cosmrs::tendermint::{Hash, block::Height};
+use futures::{StreamExt, TryStreamExt};
+use imbibe_persistence::{
+	pool::{DbConn, DbPool},
+	store::{self, WriteMode},
+};
+use tendermint_rpc::{
+	Client, Subscription, SubscriptionClient, WebSocketClient, event::EventData, query::EventType,
+};
+use tokio::{
+	sync::{broadcast, oneshot},
+	task::JoinHandle,
+};
 
-use crate::error::Result;
+use crate::{
+	BackfillIndexer, RetryPolicy,
+	error::{IndexerError, Result},
+};
 
-#[derive(Builder)]
 pub struct LiveIndexer {
 	pool: DbPool,
-	client: WebSocketClient,
+	tm_ws_url: String,
+	batch: NonZeroUsize,
+	workers: NonZeroUsize,
+	min_reconnect_backoff: Duration,
+	max_reconnect_backoff: Duration,
+	retry_policy: RetryPolicy,
+	fork_policy: ForkPolicy,
 	first_block_transmitter: Option<oneshot::Sender<NonZeroU64>>,
+
+	// receives the height of each live block once it is saved, including the blocks backfilled
+	// after a reconnect or a rollback
+	indexed_block_transmitter: Option<broadcast::Sender<NonZeroU64>>,
+}
+
+// what to do when a live block does not chain onto the stored block below it, e.g. after the chain
+// got rolled back and replayed differently
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ForkPolicy {
+	// stops indexing with `IndexerError::Fork`, leaving the stored blocks as is for inspection
+	#[default]
+	Halt,
+
+	// removes the stored blocks from the fork upwards, and reindexes them from the node
+	Rollback,
+}
+
+struct Connection {
+	client: WebSocketClient,
+	driver_handle: JoinHandle<core::result::Result<(), tendermint_rpc::Error>>,
+	subscription: Subscription,
+}
+
+#[bon::bon]
+impl LiveIndexer {
+	#[builder]
+	#[allow(clippy::too_many_arguments)]
+	pub fn new(
+		pool: DbPool,
+		#[builder(into)] tm_ws_url: String,
+		batch: NonZeroUsize,
+		workers: NonZeroUsize,
+		#[builder(default = Duration::from_secs(1))] min_reconnect_backoff: Duration,
+		#[builder(default = Duration::from_secs(60))] max_reconnect_backoff: Duration,
+		#[builder(default)] retry_policy: RetryPolicy,
+		#[builder(default)] fork_policy: ForkPolicy,
+		first_block_transmitter: Option<oneshot::Sender<NonZeroU64>>,
+		indexed_block_transmitter: Option<broadcast::Sender<NonZeroU64>>,
+	) -> Result<Self> {
+		if min_reconnect_backoff > max_reconnect_backoff {
+			return Err(IndexerError::Other(
+				"max reconnect backoff must be greater than or equal to min reconnect backoff"
+					.into(),
+			));
+		}
+
+		Ok(LiveIndexer {
+			pool,
+			tm_ws_url,
+			batch,
+			workers,
+			min_reconnect_backoff,
+			max_reconnect_backoff,
+			retry_policy,
+			fork_policy,
+			first_block_transmitter,
+			indexed_block_transmitter,
+		})
+	}
 }
 
 impl LiveIndexer {
 	#[tracing::instrument(skip_all)]
 	pub async fn start(mut self) -> Result<()> {
-		let mut subscription = self.client.subscribe(EventType::NewBlock.into()).await?;
-
-		let (first_block, first_block_id, result) = loop {
-			if let Some(Ok(event)) = subscription.next().await {
-				if let EventData::NewBlock {
-					block: Some(block),
-					block_id,
-					result_finalize_block: Some(result),
-				} = event.data
-				{
-					break (block, block_id, result);
-				}
+		let mut last_indexed_height = None;
+
+		// the backoff is only reset once a live block arrives, so that a node dropping the
+		// subscription right after accepting it is not resubscribed to in a tight loop
+		let mut backoff = self.min_reconnect_backoff;
+
+		loop {
+			let Connection { client, driver_handle, subscription } =
+				self.connect(&mut backoff).await;
+
+			let res = self
+				.index_subscription(
+					&client,
+					subscription,
+					&mut last_indexed_height,
+					&mut backoff,
+				)
+				.await;
+
+			if let Err(e) = client.close() {
+				tracing::debug!("failed to close websocket client: {e}");
+			}
+
+			match driver_handle.await {
+				Ok(Err(e)) => tracing::warn!("websocket driver error: {e}"),
+				Err(e) => tracing::warn!("websocket driver task error: {e}"),
+				Ok(Ok(())) => {},
 			}
-		};
 
-		let first_block_height = first_block.header.height;
+			if let Err(e) = &res {
+				super::metrics::record_error(e);
+			}
+
+			match res {
+				Ok(()) => {
+					tracing::warn!("live block subscription dropped, reconnecting in {backoff:?}")
+				},
+				Err(IndexerError::Rpc(e)) => {
+					tracing::warn!("rpc error: {e}, reconnecting in {backoff:?}")
+				},
+				Err(e) => return Err(e),
+			}
 
-		tracing::info!("received first live block {}", first_block_height);
+			self.back_off(&mut backoff).await;
+		}
+	}
 
-		if let Some(transmitter) = self.first_block_transmitter.take() {
-			let height = first_block_height.value().try_into().unwrap();
+	async fn connect(&self, backoff: &mut Duration) -> Connection {
+		loop {
+			match self.try_connect().await {
+				Ok(connection) => {
+					tracing::info!("subscribed to live blocks at {}", self.tm_ws_url);
+					return connection;
+				},
+				Err(e) => {
+					super::metrics::record_error(&e);
+					tracing::warn!(
+						"failed to subscribe to live blocks at {}: {e}, retrying in {backoff:?}",
+						self.tm_ws_url,
+					);
 
-			if let Err(height) = transmitter.send(height) {
-				tracing::error!("no receiver to receive first live block height {}", height);
+					self.back_off(backoff).await;
+				},
 			}
 		}
+	}
 
-		let (first_block, tx_results) = super::process_block(
-			first_block.header,
-			first_block_id.hash,
-			first_block.data,
-			result.tx_results,
-		)?;
+	async fn back_off(&self, backoff: &mut Duration) {
+		tokio::time::sleep(*backoff).await;
+		*backoff = backoff.saturating_mul(2).min(self.max_reconnect_backoff);
+	}
+
+	async fn try_connect(&self) -> Result<Connection> {
+		let (client, driver) = WebSocketClient::new(self.tm_ws_url.as_str()).await?;
+		let driver_handle = tokio::spawn(driver.run());
+
+		match client.subscribe(EventType::NewBlock.into()).await {
+			Ok(subscription) => Ok(Connection { client, driver_handle, subscription }),
+			Err(e) => {
+				let _ = client.close();
+				driver_handle.abort();
+				Err(e.into())
+			},
+		}
+	}
 
-		store::save_block_with_txs(&mut self.pool.get().await?, &first_block, &tx_results).await?;
+	async fn index_subscription(
+		&mut self,
+		client: &WebSocketClient,
+		mut subscription: Subscription,
+		last_indexed_height: &mut Option<NonZeroU64>,
+		backoff: &mut Duration,
+	) -> Result<()> {
+		let mut is_first_block = true;
 
-		while let Some(Ok(event)) = subscription.next().await {
-			if let EventData::NewBlock {
+		while let Some(event) = subscription.next().await {
+			let event = match event {
+				Ok(event) => event,
+				Err(e) => {
+					tracing::warn!("live block subscription error: {e}");
+					super::metrics::record_error(&e.into());
+					break;
+				},
+			};
+
+			let EventData::NewBlock {
 				block: Some(block),
 				block_id,
 				result_finalize_block: Some(result),
 			} = event.data
-			{
-				tracing::info!("received live block {}", block.header.height);
-
-				let (block, tx_results) = super::process_block(
-					block.header,
-					block_id.hash,
-					block.data,
-					result.tx_results,
-				)?;
-
-				store::save_block_with_txs(&mut self.pool.get().await?, &block, &tx_results)
-					.await?;
+			else {
+				continue;
+			};
+
+			let height = NonZeroU64::try_from(block.header.height.value())
+				.map_err(|_| IndexerError::Height)?;
+
+			super::metrics::record_chain_head_height(height.get());
+
+			if is_first_block {
+				is_first_block = false;
+				*backoff = self.min_reconnect_backoff;
+				tracing::info!("received first live block {height}");
+				self.on_first_block(client, height, *last_indexed_height).await?;
+			} else {
+				tracing::info!("received live block {height}");
+			}
+
+			if let Some(last_block_id) = block.header.last_block_id {
+				self.check_chain(
+					client,
+					block.header.chain_id.as_str(),
+					height,
+					last_block_id.hash,
+					last_indexed_height,
+				)
+				.await?;
 			}
+
+			let (block, tx_results, events, commit_signatures, evidence) = super::process_block(
+				block.header,
+				block_id.hash,
+				block.data,
+				result.tx_results,
+				result.events,
+				block.last_commit,
+				block.evidence,
+			)?;
+
+			super::index_validator_set(client, &self.pool, block.header()).await?;
+
+			let mut conn = self.pool.get().await?;
+			store::save_block_with_txs(
+				&mut conn,
+				&block,
+				&tx_results,
+				&events,
+				&commit_signatures,
+				&evidence,
+				WriteMode::Idempotent,
+			)
+			.await?;
+			super::metrics::record_indexed_blocks(1, tx_results.len());
+			super::advance_indexed_height(&mut conn, block.header().chain_id()).await?;
+
+			*last_indexed_height = Some(height);
+
+			self.publish(&mut conn, height).await;
 		}
 
-		tracing::info!("indexing finished");
+		Ok(())
+	}
+
+	async fn on_first_block(
+		&mut self,
+		client: &WebSocketClient,
+		height: NonZeroU64,
+		last_indexed_height: Option<NonZeroU64>,
+	) -> Result<()> {
+		if let Some(transmitter) = self.first_block_transmitter.take()
+			&& let Err(height) = transmitter.send(height)
+		{
+			tracing::error!("no receiver to receive first live block height {}", height);
+		}
+
+		let Some(lo) = last_indexed_height.and_then(|h| h.checked_add(1)) else {
+			return Ok(());
+		};
+
+		let Some(hi) = NonZeroU64::new(height.get() - 1).filter(|hi| *hi >= lo) else {
+			return Ok(());
+		};
+
+		tracing::info!("backfilling live blocks missed while reconnecting from {lo} upto {hi}");
+
+		self.backfill(client, lo, hi).await
+	}
+
+	// verifies that the block at the height, which chains onto the given hash, chains onto the
+	// stored block below it; on a fork, either halts or rolls back the stored blocks down to where
+	// the node's blocks chain onto them again, as per the fork policy.
+	//
+	// The removal commits, lowering the indexed height below the fork, before the removed blocks
+	// are reindexed. Hence if the reindexing fails, the blocks are not lost: the last indexed height
+	// is lowered along, so that a reconnect backfills them as missed, and so does a restart from the
+	// lowered indexed height
+	async fn check_chain(
+		&self,
+		client: &WebSocketClient,
+		chain_id: &str,
+		height: NonZeroU64,
+		last_block_hash: Hash,
+		last_indexed_height: &mut Option<NonZeroU64>,
+	) -> Result<()> {
+		let mut conn = self.pool.get().await?;
+		let mut fork_height = None;
+		let (mut above, mut last_block_hash) = (height, last_block_hash);
+
+		while let Some(below) = NonZeroU64::new(above.get() - 1)
+			&& let Some(stored) = store::fetch_block_hash(&mut conn, below).await?
+		{
+			let expected = super::make_sha256(last_block_hash).ok_or(IndexerError::BlockHash)?;
+			if stored.get() == expected.get() {
+				break;
+			}
+
+			tracing::error!(
+				"fork detected: block {below} is stored with hash {stored}, but the block {above} \
+				 of the node chains onto {expected}"
+			);
+
+			if self.fork_policy == ForkPolicy::Halt {
+				super::metrics::record_fork("halt");
+				return Err(IndexerError::Fork { height: below.get(), stored, expected });
+			}
+
+			fork_height = Some(below);
+
+			let rpc_height = Height::try_from(below.get()).map_err(|_| IndexerError::RpcHeight)?;
+			let Some(last_block_id) = client.block(rpc_height).await?.block.header.last_block_id
+			else {
+				break;
+			};
+
+			(above, last_block_hash) = (below, last_block_id.hash);
+		}
+
+		let Some(fork_height) = fork_height else {
+			return Ok(());
+		};
+
+		super::metrics::record_fork("rollback");
+
+		let removed = store::remove_blocks_from(&mut conn, chain_id, fork_height).await?;
+		tracing::error!("rolled back {removed} stored blocks from block {fork_height} due to fork");
+		super::metrics::record_rolled_back_blocks(removed);
+		drop(conn);
+
+		if let Some(below) = NonZeroU64::new(fork_height.get() - 1) {
+			*last_indexed_height = Some(last_indexed_height.map_or(below, |h| h.min(below)));
+		}
+
+		match NonZeroU64::new(height.get() - 1) {
+			Some(hi) if hi >= fork_height => self.backfill(client, fork_height, hi).await,
+			_ => Ok(()),
+		}
+	}
+
+	async fn backfill(
+		&self,
+		client: &WebSocketClient,
+		lo: NonZeroU64,
+		hi: NonZeroU64,
+	) -> Result<()> {
+		BackfillIndexer::builder()
+			.pool(self.pool.clone())
+			.client(client.clone())
+			.batch(self.batch)
+			.workers(self.workers)
+			.lo(lo)
+			.hi(hi)
+			.retry_policy(self.retry_policy)
+			.build()?
+			.start()
+			.await?;
+
+		// the backfilled blocks are saved out of order, hence they are published in order once all
+		// of them are saved, skipping the ones that failed to be saved
+		let mut conn = self.pool.get().await?;
+		let missing = store::fetch_missing_block_heights(&mut conn, lo, hi)
+			.await?
+			.try_collect::<HashSet<_>>()
+			.await?;
+
+		for height in (lo.get()..=hi.get()).filter_map(NonZeroU64::new) {
+			if !missing.contains(&height) {
+				self.publish(&mut conn, height).await;
+			}
+		}
 
 		Ok(())
 	}
+
+	// publishes the saved block to the subscribers of the queriers
+	async fn publish(&self, conn: &mut DbConn, height: NonZeroU64) {
+		// the block is saved regardless, hence failing to publish it is not fatal
+		if let Err(e) = store::notify_indexed_block(conn, height).await {
+			tracing::warn!("failed to notify indexed block {height}: {e}");
+		}
+
+		if let Some(transmitter) = &self.indexed_block_transmitter {
+			// fails only when there are no receivers
+			let _ = transmitter.send(height);
+		}
+	}
 }
@@ -0,0 +1,95 @@
+use core::sync::atomic::{AtomicU64, Ordering};
+
+use metrics::{Unit, counter, describe_counter, describe_gauge, gauge};
+
+use crate::error::IndexerError;
+
+const INDEXED_HEIGHT: &str = "imbibe_indexer_indexed_height";
+const CHAIN_HEAD_HEIGHT: &str = "imbibe_indexer_chain_head_height";
+const LAG: &str = "imbibe_indexer_lag_blocks";
+const INDEXED_BLOCKS: &str = "imbibe_indexer_indexed_blocks_total";
+const INDEXED_TXS: &str = "imbibe_indexer_indexed_txs_total";
+const ERRORS: &str = "imbibe_indexer_errors_total";
+const FORKS: &str = "imbibe_indexer_forks_total";
+const ROLLED_BACK_BLOCKS: &str = "imbibe_indexer_rolled_back_blocks_total";
+
+// the last recorded heights, from which the lag is derived
+static LAST_INDEXED_HEIGHT: AtomicU64 = AtomicU64::new(0);
+static LAST_CHAIN_HEAD_HEIGHT: AtomicU64 = AtomicU64::new(0);
+
+pub fn describe() {
+	describe_gauge!(
+		INDEXED_HEIGHT,
+		"height upto which the chain is indexed contiguously"
+	);
+	describe_gauge!(
+		CHAIN_HEAD_HEIGHT,
+		"height of the latest block seen on the node"
+	);
+	describe_gauge!(
+		LAG,
+		Unit::Count,
+		"number of blocks the indexed height is behind the chain head height"
+	);
+	describe_counter!(
+		INDEXED_BLOCKS,
+		Unit::Count,
+		"number of blocks saved by the live and backfill indexers"
+	);
+	describe_counter!(
+		INDEXED_TXS,
+		Unit::Count,
+		"number of txs saved by the live and backfill indexers"
+	);
+	describe_counter!(
+		ERRORS,
+		Unit::Count,
+		"number of indexer errors, including the failed attempts which are retried, by kind"
+	);
+	describe_counter!(
+		FORKS,
+		Unit::Count,
+		"number of forks detected by the live indexer, by the policy applied"
+	);
+	describe_counter!(
+		ROLLED_BACK_BLOCKS,
+		Unit::Count,
+		"number of stored blocks removed by the live indexer to roll back forks"
+	);
+}
+
+pub(crate) fn record_indexed_height(height: u64) {
+	LAST_INDEXED_HEIGHT.store(height, Ordering::Relaxed);
+	gauge!(INDEXED_HEIGHT).set(height as f64);
+	record_lag();
+}
+
+pub(crate) fn record_chain_head_height(height: u64) {
+	LAST_CHAIN_HEAD_HEIGHT.store(height, Ordering::Relaxed);
+	gauge!(CHAIN_HEAD_HEIGHT).set(height as f64);
+	record_lag();
+}
+
+pub(crate) fn record_indexed_blocks(blocks: usize, txs: usize) {
+	counter!(INDEXED_BLOCKS).increment(blocks as u64);
+	counter!(INDEXED_TXS).increment(txs as u64);
+}
+
+pub(crate) fn record_error(error: &IndexerError) {
+	counter!(ERRORS, "kind" => error.kind()).increment(1);
+}
+
+pub(crate) fn record_fork(policy: &'static str) {
+	counter!(FORKS, "policy" => policy).increment(1);
+}
+
+pub(crate) fn record_rolled_back_blocks(blocks: usize) {
+	counter!(ROLLED_BACK_BLOCKS).increment(blocks as u64);
+}
+
+fn record_lag() {
+	let head = LAST_CHAIN_HEAD_HEIGHT.load(Ordering::Relaxed);
+	let indexed = LAST_INDEXED_HEIGHT.load(Ordering::Relaxed);
+
+	gauge!(LAG).set(head.saturating_sub(indexed) as f64);
+}
@@ -0,0 +1,150 @@
+use core::{num::NonZeroU32, time::Duration};
+
+use bon::Builder;
+
+use crate::error::{IndexerError, Result};
+
+#[derive(Debug, Clone, Copy, Builder)]
+pub struct RetryPolicy {
+	#[builder(default = NonZeroU32::new(5).expect("must be positive"))]
+	max_attempts: NonZeroU32,
+
+	#[builder(default = Duration::from_millis(500))]
+	min_backoff: Duration,
+
+	#[builder(default = Duration::from_secs(30))]
+	max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+	fn default() -> Self {
+		Self::builder().build()
+	}
+}
+
+impl RetryPolicy {
+	pub fn max_attempts(&self) -> NonZeroU32 {
+		self.max_attempts
+	}
+
+	pub fn min_backoff(&self) -> Duration {
+		self.min_backoff
+	}
+
+	pub fn max_backoff(&self) -> Duration {
+		self.max_backoff
+	}
+
+	// retries transient errors with exponential backoff, and returns the number of attempts made
+	// along with the last error once the attempts are exhausted or the error is not transient
+	pub(crate) async fn retry<T, F, Fut>(&self, mut f: F) -> Result<T, (NonZeroU32, IndexerError)>
+	where
+		F: FnMut() -> Fut,
+		Fut: Future<Output = Result<T>>,
+	{
+		let mut attempts = NonZeroU32::MIN;
+		let mut backoff = self.min_backoff;
+
+		loop {
+			let res = f().await.inspect_err(crate::metrics::record_error);
+
+			match res {
+				Ok(t) => return Ok(t),
+				Err(e) if !e.is_transient() || attempts >= self.max_attempts => {
+					return Err((attempts, e));
+				},
+				Err(e) => {
+					tracing::warn!("attempt {attempts} failed: {e}, retrying in {backoff:?}");
+
+					tokio::time::sleep(backoff).await;
+					backoff = self.next_backoff(backoff);
+					attempts = attempts.saturating_add(1);
+				},
+			}
+		}
+	}
+
+	fn next_backoff(&self, backoff: Duration) -> Duration {
+		backoff.saturating_mul(2).min(self.max_backoff)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use core::sync::atomic::{AtomicU32, Ordering};
+
+	use super::*;
+
+	fn policy(max_attempts: u32) -> RetryPolicy {
+		RetryPolicy::builder()
+			.max_attempts(NonZeroU32::new(max_attempts).unwrap())
+			.min_backoff(Duration::from_millis(100))
+			.max_backoff(Duration::from_millis(500))
+			.build()
+	}
+
+	fn transient() -> IndexerError {
+		tendermint_rpc::Error::timeout(Duration::from_secs(1)).into()
+	}
+
+	#[test]
+	fn backoff_doubles_upto_max() {
+		let policy = policy(5);
+		let backoffs = core::iter::successors(Some(policy.min_backoff()), |b| {
+			Some(policy.next_backoff(*b))
+		})
+		.take(5)
+		.map(|b| b.as_millis())
+		.collect::<Vec<_>>();
+
+		assert_eq!(backoffs, [100, 200, 400, 500, 500]);
+	}
+
+	#[tokio::test(start_paused = true)]
+	async fn retries_transient_errors_until_success() {
+		let calls = AtomicU32::new(0);
+		let started = tokio::time::Instant::now();
+
+		let res = policy(5)
+			.retry(|| async {
+				match calls.fetch_add(1, Ordering::Relaxed) {
+					0..2 => Err(transient()),
+					n => Ok(n),
+				}
+			})
+			.await;
+
+		assert!(matches!(res, Ok(2)));
+		assert_eq!(started.elapsed(), Duration::from_millis(300));
+	}
+
+	#[tokio::test(start_paused = true)]
+	async fn gives_up_after_max_attempts() {
+		let calls = AtomicU32::new(0);
+
+		let res = policy(3)
+			.retry(|| async {
+				calls.fetch_add(1, Ordering::Relaxed);
+				Result::<()>::Err(transient())
+			})
+			.await;
+
+		assert!(matches!(res, Err((attempts, IndexerError::Rpc(_))) if attempts.get() == 3));
+		assert_eq!(calls.load(Ordering::Relaxed), 3);
+	}
+
+	#[tokio::test(start_paused = true)]
+	async fn does_not_retry_permanent_errors() {
+		let calls = AtomicU32::new(0);
+
+		let res = policy(3)
+			.retry(|| async {
+				calls.fetch_add(1, Ordering::Relaxed);
+				Result::<()>::Err(IndexerError::Height)
+			})
+			.await;
+
+		assert!(matches!(res, Err((attempts, IndexerError::Height)) if attempts.get() == 1));
+		assert_eq!(calls.load(Ordering::Relaxed), 1);
+	}
+}
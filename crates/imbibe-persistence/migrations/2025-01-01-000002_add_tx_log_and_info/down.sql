@@ -0,0 +1,7 @@
+-- This file should undo anything in `up.sql`
+
+ALTER TABLE tx
+    DROP CONSTRAINT IF EXISTS chk_info_not_empty,
+    DROP CONSTRAINT IF EXISTS chk_log_not_empty,
+    DROP COLUMN IF EXISTS info,
+    DROP COLUMN IF EXISTS log;
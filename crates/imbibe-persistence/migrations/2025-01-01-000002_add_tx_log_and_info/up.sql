@@ -0,0 +1,10 @@
+ALTER TABLE tx
+    ADD COLUMN IF NOT EXISTS log TEXT,
+    ADD COLUMN IF NOT EXISTS info TEXT,
+
+    ADD CONSTRAINT chk_log_not_empty CHECK (
+        log IS NULL OR LENGTH(log) > 0
+    ),
+    ADD CONSTRAINT chk_info_not_empty CHECK (
+        info IS NULL OR LENGTH(info) > 0
+    );
@@ -0,0 +1,34 @@
+-- `event_idx_in_block` orders all events of a block: the block level events (begin/end block and
+-- finalize block) in the order the node returns them, followed by the events emitted by each tx in
+-- the order of txs in the block, so that the indices of block level events do not depend on the
+-- number of txs. Block level events do not belong to any tx and have `tx_idx_in_block` set to NULL.
+CREATE TABLE IF NOT EXISTS event (
+    block_height BIGINT NOT NULL REFERENCES block(height),
+    event_idx_in_block BIGINT NOT NULL,
+
+    tx_idx_in_block BIGINT,
+    kind TEXT NOT NULL,
+
+    PRIMARY KEY (block_height, event_idx_in_block),
+    FOREIGN KEY (block_height, tx_idx_in_block) REFERENCES tx(block_height, tx_idx_in_block)
+);
+
+CREATE TABLE IF NOT EXISTS event_attribute (
+    block_height BIGINT NOT NULL REFERENCES block(height),
+    event_idx_in_block BIGINT NOT NULL,
+    attribute_idx_in_event BIGINT NOT NULL,
+
+    key TEXT NOT NULL,
+    value TEXT NOT NULL,
+    index BOOLEAN NOT NULL,
+
+    PRIMARY KEY (block_height, event_idx_in_block, attribute_idx_in_event),
+    FOREIGN KEY (block_height, event_idx_in_block) REFERENCES event(block_height, event_idx_in_block)
+);
+
+CREATE INDEX IF NOT EXISTS idx_event_tx ON event (block_height, tx_idx_in_block);
+CREATE INDEX IF NOT EXISTS idx_event_kind ON event (kind);
+CREATE INDEX IF NOT EXISTS idx_event_attribute_key ON event_attribute (key);
+
+-- attribute values may exceed the btree row size limit, hence hash index
+CREATE INDEX IF NOT EXISTS idx_event_attribute_value ON event_attribute USING HASH (value);
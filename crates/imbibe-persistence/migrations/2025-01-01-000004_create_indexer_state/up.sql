@@ -0,0 +1,17 @@
+-- `indexed_height` is the contiguous watermark of a chain: every block from `start_height` upto and
+-- including this height has been indexed, whereas `start_height - 1` denotes that no block has been
+-- indexed yet. `start_height` is the height the watermark counts from, as blocks below the
+-- configured start height or pruned off the node are never indexed; it is only ever raised.
+CREATE TABLE IF NOT EXISTS indexer_state (
+    chain_id TEXT PRIMARY KEY,
+
+    indexed_height BIGINT NOT NULL,
+    start_height BIGINT NOT NULL DEFAULT 1,
+
+    CONSTRAINT chk_indexed_height_non_negative CHECK (
+        indexed_height >= 0
+    ),
+    CONSTRAINT chk_start_height_positive CHECK (
+        start_height > 0
+    )
+);
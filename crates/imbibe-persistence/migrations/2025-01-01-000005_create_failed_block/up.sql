@@ -0,0 +1,19 @@
+-- dead letters of the blocks that could not be indexed even after retries, kept around so that
+-- they can be re-driven later
+CREATE TABLE IF NOT EXISTS failed_block (
+    chain_id TEXT NOT NULL,
+    height BIGINT NOT NULL,
+
+    attempts INTEGER NOT NULL,
+    error TEXT NOT NULL,
+    failed_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    PRIMARY KEY (chain_id, height),
+
+    CONSTRAINT chk_failed_block_height_positive CHECK (
+        height > 0
+    ),
+    CONSTRAINT chk_failed_block_attempts_positive CHECK (
+        attempts > 0
+    )
+);
@@ -0,0 +1,16 @@
+-- accounts involved in a tx: its fee payer, fee granter, signers and msg signers
+CREATE TABLE IF NOT EXISTS account_tx (
+    address BYTEA NOT NULL,
+    block_height BIGINT NOT NULL REFERENCES block(height),
+    tx_idx_in_block BIGINT NOT NULL,
+
+    -- leading with the address allows paginating the txs of an account in the key order
+    PRIMARY KEY (address, block_height, tx_idx_in_block),
+    FOREIGN KEY (block_height, tx_idx_in_block) REFERENCES tx(block_height, tx_idx_in_block),
+
+    CONSTRAINT chk_account_tx_address_len CHECK (
+        OCTET_LENGTH(address) = 20
+    )
+);
+
+CREATE INDEX IF NOT EXISTS idx_account_tx_tx ON account_tx (block_height, tx_idx_in_block);
@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+
+ALTER TABLE msg
+    DROP CONSTRAINT IF EXISTS chk_msg_json_object,
+    DROP COLUMN IF EXISTS json;
@@ -0,0 +1,7 @@
+-- canonical proto3 JSON of the msg, if the indexer was able to decode it
+ALTER TABLE msg
+    ADD COLUMN IF NOT EXISTS json JSONB,
+
+    ADD CONSTRAINT chk_msg_json_object CHECK (
+        json IS NULL OR JSONB_TYPEOF(json) = 'object'
+    );
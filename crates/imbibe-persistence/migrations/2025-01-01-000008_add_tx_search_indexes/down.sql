@@ -0,0 +1,6 @@
+-- This file should undo anything in `up.sql`
+
+DROP INDEX IF EXISTS idx_tx_memo;
+DROP INDEX IF EXISTS idx_tx_codespace;
+DROP INDEX IF EXISTS idx_tx_code;
+DROP INDEX IF EXISTS idx_msg_type_url;
@@ -0,0 +1,19 @@
+-- the trailing tx position columns let the search paginate in index order
+CREATE INDEX IF NOT EXISTS idx_msg_type_url ON msg (type_url, block_height, tx_idx_in_block);
+CREATE INDEX IF NOT EXISTS idx_tx_code ON tx (code, block_height, tx_idx_in_block);
+CREATE INDEX IF NOT EXISTS idx_tx_codespace ON tx (codespace, block_height, tx_idx_in_block);
+
+-- memo substring search, backed by a pg_trgm index if the extension is there or can be created.
+-- Creating it requires the CREATE privilege on the database, as it is a trusted extension since
+-- PostgreSQL 13, or else superuser; without it the search still works, but scans the txs, until
+-- the extension and the index are created by a user holding the privilege, e.g. with
+-- `CREATE EXTENSION IF NOT EXISTS pg_trgm;` and the index statement below
+DO $$
+BEGIN
+	CREATE EXTENSION IF NOT EXISTS pg_trgm;
+	CREATE INDEX IF NOT EXISTS idx_tx_memo ON tx USING GIN (memo gin_trgm_ops);
+EXCEPTION
+	WHEN insufficient_privilege OR undefined_file THEN
+		RAISE WARNING 'skipping the memo search index, as the pg_trgm extension cannot be created: %', SQLERRM;
+END
+$$;
@@ -0,0 +1,2 @@
+-- looking up blocks by time, and mapping time ranges to height ranges
+CREATE INDEX IF NOT EXISTS idx_block_time ON block (time);
@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+
+DROP INDEX IF EXISTS idx_block_validators_hash;
+DROP TABLE IF EXISTS commit_signature;
+DROP TABLE IF EXISTS validator_set;
@@ -0,0 +1,54 @@
+-- members of the validator sets, keyed by the validators hash of the block headers using them
+CREATE TABLE IF NOT EXISTS validator_set (
+    validators_hash BYTEA NOT NULL,
+    validator_idx BIGINT NOT NULL,
+
+    address BYTEA NOT NULL,
+    pub_key_type TEXT NOT NULL,
+    pub_key BYTEA NOT NULL,
+    voting_power BIGINT NOT NULL,
+
+    PRIMARY KEY (validators_hash, validator_idx),
+
+    CONSTRAINT chk_validator_set_validators_hash_len CHECK (
+        OCTET_LENGTH(validators_hash) = 32
+    ),
+    CONSTRAINT chk_validator_set_address_len CHECK (
+        OCTET_LENGTH(address) = 20
+    ),
+    CONSTRAINT chk_validator_set_pub_key_type CHECK (
+        pub_key_type IN ('ed25519', 'secp256k1')
+    ),
+    CONSTRAINT chk_validator_set_voting_power_non_negative CHECK (
+        voting_power >= 0
+    )
+);
+
+CREATE INDEX IF NOT EXISTS idx_validator_set_address ON validator_set (address);
+
+-- finds the blocks a validator set was in charge of
+CREATE INDEX IF NOT EXISTS idx_block_validators_hash ON block (validators_hash);
+
+-- votes of the last commit included in a block, i.e. the commit of the block below it, in the
+-- order of the validator set of the committed block
+CREATE TABLE IF NOT EXISTS commit_signature (
+    block_height BIGINT NOT NULL REFERENCES block(height),
+    validator_idx BIGINT NOT NULL,
+
+    -- as per the cometbft BlockIDFlag: 1 for absent, 2 for commit, 3 for nil
+    flag SMALLINT NOT NULL,
+    validator_address BYTEA,
+    time TIMESTAMPTZ,
+
+    PRIMARY KEY (block_height, validator_idx),
+
+    CONSTRAINT chk_commit_signature_flag CHECK (
+        flag IN (1, 2, 3)
+    ),
+    CONSTRAINT chk_commit_signature_validator_address_len CHECK (
+        validator_address IS NULL OR OCTET_LENGTH(validator_address) = 20
+    ),
+    CONSTRAINT chk_commit_signature_absent CHECK (
+        (flag = 1) = (validator_address IS NULL AND time IS NULL)
+    )
+);
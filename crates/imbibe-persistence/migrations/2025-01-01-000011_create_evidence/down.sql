@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+
+DROP TABLE IF EXISTS evidence_validator;
+DROP TABLE IF EXISTS evidence;
@@ -0,0 +1,46 @@
+CREATE TABLE IF NOT EXISTS evidence (
+    block_height BIGINT NOT NULL REFERENCES block(height),
+    evidence_idx_in_block BIGINT NOT NULL,
+
+    kind TEXT NOT NULL,
+    height BIGINT NOT NULL,
+    time TIMESTAMPTZ NOT NULL,
+    total_voting_power BIGINT NOT NULL,
+
+    -- set for duplicate vote evidence only
+    validator_power BIGINT,
+
+    -- set for light client attack evidence only
+    common_height BIGINT,
+
+    PRIMARY KEY (block_height, evidence_idx_in_block),
+
+    CONSTRAINT chk_evidence_kind CHECK (
+        (kind = 'duplicate_vote' AND validator_power IS NOT NULL AND common_height IS NULL)
+        OR (kind = 'light_client_attack' AND validator_power IS NULL AND common_height IS NOT NULL)
+    ),
+    CONSTRAINT chk_evidence_height_positive CHECK (
+        height > 0
+    ),
+    CONSTRAINT chk_evidence_total_voting_power_non_negative CHECK (
+        total_voting_power >= 0
+    )
+);
+
+-- offending validators of an evidence
+CREATE TABLE IF NOT EXISTS evidence_validator (
+    block_height BIGINT NOT NULL,
+    evidence_idx_in_block BIGINT NOT NULL,
+    validator_idx_in_evidence BIGINT NOT NULL,
+
+    address BYTEA NOT NULL,
+
+    PRIMARY KEY (block_height, evidence_idx_in_block, validator_idx_in_evidence),
+    FOREIGN KEY (block_height, evidence_idx_in_block) REFERENCES evidence(block_height, evidence_idx_in_block),
+
+    CONSTRAINT chk_evidence_validator_address_len CHECK (
+        OCTET_LENGTH(address) = 20
+    )
+);
+
+CREATE INDEX IF NOT EXISTS idx_evidence_validator_address ON evidence_validator (address, block_height, evidence_idx_in_block);
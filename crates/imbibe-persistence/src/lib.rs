@@ -1,3 +1,8 @@
+pub mod migration;
+
+#[cfg(feature = "database")]
+pub mod metrics;
+
 pub mod pool;
 pub mod store;
 
@@ -0,0 +1,45 @@
+use core::time::Duration;
+
+use metrics::{Unit, describe_gauge, describe_histogram, gauge, histogram};
+
+use crate::pool::DbPool;
+
+const SAVE_BLOCKS_DURATION: &str = "imbibe_store_save_blocks_duration_seconds";
+const POOL_IN_USE_CONNECTIONS: &str = "imbibe_db_pool_in_use_connections";
+const POOL_MAX_CONNECTIONS: &str = "imbibe_db_pool_max_connections";
+const POOL_WAITING_REQUESTS: &str = "imbibe_db_pool_waiting_requests";
+
+pub fn describe() {
+	describe_histogram!(
+		SAVE_BLOCKS_DURATION,
+		Unit::Seconds,
+		"latency of saving a batch of blocks with their txs in a single transaction"
+	);
+	describe_gauge!(
+		POOL_IN_USE_CONNECTIONS,
+		"number of connections of the db pool checked out"
+	);
+	describe_gauge!(
+		POOL_MAX_CONNECTIONS,
+		"maximum number of connections of the db pool"
+	);
+	describe_gauge!(
+		POOL_WAITING_REQUESTS,
+		"number of requests waiting for a connection of the db pool"
+	);
+}
+
+// samples the status of the pool, which saturates once every connection is in use and the
+// requests start waiting
+pub fn record_pool_status(pool: &DbPool) {
+	let status = pool.status();
+
+	gauge!(POOL_IN_USE_CONNECTIONS).set(status.size.saturating_sub(status.available) as f64);
+	gauge!(POOL_MAX_CONNECTIONS).set(status.max_size as f64);
+	gauge!(POOL_WAITING_REQUESTS).set(status.waiting as f64);
+}
+
+pub(crate) fn record_save_blocks(duration: Duration, saved: bool) {
+	histogram!(SAVE_BLOCKS_DURATION, "outcome" => if saved { "ok" } else { "error" })
+		.record(duration);
+}
@@ -0,0 +1,126 @@
+#[cfg(feature = "database")]
+pub use self::database::*;
+
+#[derive(Debug, thiserror::Error)]
+pub enum MigrationError {
+	#[error("connection error: {0}")]
+	Connection(#[from] diesel::ConnectionError),
+
+	#[error("db error: {0}")]
+	Db(#[from] diesel::result::Error),
+
+	#[error("migration error: {0}")]
+	Migration(Box<dyn std::error::Error + Send + Sync>),
+
+	#[error(
+		"pending migrations error: the database schema is missing the migrations {}",
+		.0.join(", ")
+	)]
+	Pending(Vec<String>),
+
+	#[error(
+		"schema newer error: the database schema has the migrations {} unknown to this binary, \
+		 which must be upgraded to a version shipping them",
+		.0.join(", ")
+	)]
+	SchemaNewer(Vec<String>),
+}
+
+#[cfg(feature = "database")]
+mod database {
+	use std::collections::BTreeSet;
+
+	use diesel::{
+		Connection, PgConnection, QueryableByName, RunQueryDsl,
+		migration::MigrationSource,
+		pg::Pg,
+		sql_types::{Bool, Text},
+	};
+	use diesel_migrations::{EmbeddedMigrations, MigrationHarness, embed_migrations};
+
+	use super::MigrationError;
+
+	const MIGRATIONS: EmbeddedMigrations = embed_migrations!();
+
+	// applies the pending migrations, returning their versions; fails without applying any if the
+	// database schema is newer than the embedded migrations
+	#[tracing::instrument(skip(db_url))]
+	pub fn run_pending_migrations(db_url: &str) -> Result<Vec<String>, MigrationError> {
+		let mut conn = PgConnection::establish(db_url)?;
+
+		let embedded = embedded_versions()?;
+		let applied = applied_versions(&mut conn)?;
+		check_not_newer(&embedded, &applied)?;
+
+		conn.run_pending_migrations(MIGRATIONS)
+			.map(|versions| versions.into_iter().map(|v| v.to_string()).collect())
+			.map_err(MigrationError::Migration)
+	}
+
+	// verifies that the database schema matches the embedded migrations, without writing to the
+	// database
+	#[tracing::instrument(skip(db_url))]
+	pub fn verify_migrations(db_url: &str) -> Result<(), MigrationError> {
+		let mut conn = PgConnection::establish(db_url)?;
+
+		let embedded = embedded_versions()?;
+		let applied = conn.build_transaction().read_only().run(applied_versions)?;
+		check_not_newer(&embedded, &applied)?;
+
+		let pending = embedded.difference(&applied).cloned().collect::<Vec<_>>();
+		if !pending.is_empty() {
+			return Err(MigrationError::Pending(pending));
+		}
+
+		Ok(())
+	}
+
+	fn check_not_newer(
+		embedded: &BTreeSet<String>,
+		applied: &BTreeSet<String>,
+	) -> Result<(), MigrationError> {
+		let unknown = applied.difference(embedded).cloned().collect::<Vec<_>>();
+		if !unknown.is_empty() {
+			return Err(MigrationError::SchemaNewer(unknown));
+		}
+
+		Ok(())
+	}
+
+	fn embedded_versions() -> Result<BTreeSet<String>, MigrationError> {
+		MigrationSource::<Pg>::migrations(&MIGRATIONS)
+			.map_err(MigrationError::Migration)
+			.map(|migrations| migrations.iter().map(|m| m.name().version().to_string()).collect())
+	}
+
+	// reads the applied versions off the diesel bookkeeping table directly, as the migration
+	// harness creates the table when it is missing
+	fn applied_versions(
+		conn: &mut PgConnection,
+	) -> Result<BTreeSet<String>, diesel::result::Error> {
+		#[derive(QueryableByName)]
+		struct TableExists {
+			#[diesel(sql_type = Bool)]
+			exists: bool,
+		}
+
+		#[derive(QueryableByName)]
+		struct AppliedVersion {
+			#[diesel(sql_type = Text)]
+			version: String,
+		}
+
+		let TableExists { exists } = diesel::sql_query(
+			"SELECT to_regclass('__diesel_schema_migrations') IS NOT NULL AS exists",
+		)
+		.get_result(conn)?;
+
+		if !exists {
+			return Ok(BTreeSet::new());
+		}
+
+		diesel::sql_query("SELECT version FROM __diesel_schema_migrations")
+			.load::<AppliedVersion>(conn)
+			.map(|versions| versions.into_iter().map(|v| v.version).collect())
+	}
+}
@@ -6,8 +6,26 @@ pub mod insert;
 #[cfg(feature = "database")]
 pub mod select;
 
+// as per the cometbft BlockIDFlag
 #[cfg(feature = "database")]
-fn jiff_to_chrono(jiff: &jiff::Timestamp) -> Option<chrono::DateTime<chrono::Utc>> {
+pub(crate) const BLOCK_ID_FLAG_ABSENT: i16 = 1;
+#[cfg(feature = "database")]
+const BLOCK_ID_FLAG_COMMIT: i16 = 2;
+#[cfg(feature = "database")]
+const BLOCK_ID_FLAG_NIL: i16 = 3;
+
+#[cfg(feature = "database")]
+const PUB_KEY_TYPE_ED25519: &str = "ed25519";
+#[cfg(feature = "database")]
+const PUB_KEY_TYPE_SECP256K1: &str = "secp256k1";
+
+#[cfg(feature = "database")]
+const EVIDENCE_KIND_DUPLICATE_VOTE: &str = "duplicate_vote";
+#[cfg(feature = "database")]
+const EVIDENCE_KIND_LIGHT_CLIENT_ATTACK: &str = "light_client_attack";
+
+#[cfg(feature = "database")]
+pub(crate) fn jiff_to_chrono(jiff: &jiff::Timestamp) -> Option<chrono::DateTime<chrono::Utc>> {
 	let nanos = jiff.as_nanosecond();
 
 	const NANOS_IN_ONE_SEC: i128 = 1_000_000_000;
@@ -24,3 +42,14 @@ fn chrono_to_jiff(chrono: &chrono::DateTime<chrono::Utc>) -> jiff::Timestamp {
 	let sub_nanos = chrono.timestamp_subsec_nanos().try_into().expect("sub nanos must be valid");
 	jiff::Timestamp::new(secs, sub_nanos).expect("valid datetime must yield valid timestamp")
 }
+
+#[cfg(feature = "database")]
+fn pub_key_type(pub_key: &cosmrs::tendermint::PublicKey) -> Option<&'static str> {
+	if pub_key.ed25519().is_some() {
+		Some(PUB_KEY_TYPE_ED25519)
+	} else if pub_key.secp256k1().is_some() {
+		Some(PUB_KEY_TYPE_SECP256K1)
+	} else {
+		None
+	}
+}
@@ -3,7 +3,14 @@ use bon::Builder;
 use chrono::{DateTime, Utc};
 use cosmrs::{Any, tx::SignerPublicKey};
 use diesel::prelude::Insertable;
-use imbibe_domain::{Address, block::Block, tx::Tx};
+use imbibe_domain::{
+	Address,
+	block::Block,
+	event::Event,
+	evidence::{Evidence, EvidenceKind},
+	tx::Tx,
+	validator::{CommitSignature, Validator, Vote},
+};
 use serde_json::Value;
 
 use crate::schema;
@@ -51,6 +58,8 @@ pub struct NewTxRecord<'a> {
 	codespace: Option<&'a str>,
 	data_bz: Option<&'a [u8]>,
 	tx_bz: &'a [u8],
+	log: Option<&'a str>,
+	info: Option<&'a str>,
 }
 
 #[derive(Insertable, Builder)]
@@ -63,6 +72,14 @@ pub struct NewSignatureRecord<'a> {
 	bz: &'a [u8],
 }
 
+#[derive(Insertable, Builder)]
+#[diesel(table_name = schema::account_tx)]
+pub struct NewAccountTxRecord<'a> {
+	address: &'a [u8],
+	block_height: i64,
+	tx_idx_in_block: i64,
+}
+
 #[derive(Insertable, Builder)]
 #[diesel(table_name = schema::fee)]
 pub struct NewFeeRecord<'a> {
@@ -83,6 +100,76 @@ pub struct NewMsgRecord<'a> {
 
 	type_url: &'a str,
 	value: &'a [u8],
+	json: Option<Value>,
+}
+
+#[derive(Insertable, Builder)]
+#[diesel(table_name = schema::event)]
+pub struct NewEventRecord<'a> {
+	block_height: i64,
+	event_idx_in_block: i64,
+
+	tx_idx_in_block: Option<i64>,
+	kind: &'a str,
+}
+
+#[derive(Insertable, Builder)]
+#[diesel(table_name = schema::event_attribute)]
+pub struct NewEventAttributeRecord<'a> {
+	block_height: i64,
+	event_idx_in_block: i64,
+	attribute_idx_in_event: i64,
+
+	key: &'a str,
+	value: &'a str,
+	index: bool,
+}
+
+#[derive(Insertable, Builder)]
+#[diesel(table_name = schema::validator_set)]
+pub struct NewValidatorSetRecord<'a> {
+	validators_hash: &'a [u8],
+	validator_idx: i64,
+
+	address: &'a [u8],
+	pub_key_type: &'a str,
+	pub_key: Vec<u8>,
+	voting_power: i64,
+}
+
+#[derive(Insertable, Builder)]
+#[diesel(table_name = schema::commit_signature)]
+pub struct NewCommitSignatureRecord<'a> {
+	block_height: i64,
+	validator_idx: i64,
+
+	flag: i16,
+	validator_address: Option<&'a [u8]>,
+	time: Option<DateTime<Utc>>,
+}
+
+#[derive(Insertable, Builder)]
+#[diesel(table_name = schema::evidence)]
+pub struct NewEvidenceRecord {
+	block_height: i64,
+	evidence_idx_in_block: i64,
+
+	kind: &'static str,
+	height: i64,
+	time: DateTime<Utc>,
+	total_voting_power: i64,
+	validator_power: Option<i64>,
+	common_height: Option<i64>,
+}
+
+#[derive(Insertable, Builder)]
+#[diesel(table_name = schema::evidence_validator)]
+pub struct NewEvidenceValidatorRecord<'a> {
+	block_height: i64,
+	evidence_idx_in_block: i64,
+	validator_idx_in_evidence: i64,
+
+	address: &'a [u8],
 }
 
 impl<'a> TryFrom<&'a Block> for NewBlockRecord<'a> {
@@ -132,12 +219,112 @@ impl<'a> TryFrom<&'a Tx> for NewTxRecord<'a> {
 			.maybe_codespace(tx_result.codespace().map(AsRef::as_ref))
 			.maybe_data_bz(tx_result.data_bz().map(AsRef::as_ref))
 			.tx_bz(tx_result.tx_bz().get())
+			.maybe_log(tx_result.log().map(AsRef::as_ref))
+			.maybe_info(tx_result.info().map(AsRef::as_ref))
 			.build();
 
 		Ok(tx_record)
 	}
 }
 
+impl<'a> TryFrom<&'a Event> for NewEventRecord<'a> {
+	type Error = InvalidValueError;
+
+	fn try_from(event: &'a Event) -> Result<Self, Self::Error> {
+		let record = Self::builder()
+			.block_height(event.block_height().get().try_into()?)
+			.event_idx_in_block(event.event_idx_in_block().try_into()?)
+			.maybe_tx_idx_in_block(event.tx_idx_in_block().map(TryFrom::try_from).transpose()?)
+			.kind(event.kind())
+			.build();
+
+		Ok(record)
+	}
+}
+
+impl<'a> TryFrom<&'a CommitSignature> for NewCommitSignatureRecord<'a> {
+	type Error = InvalidValueError;
+
+	fn try_from(signature: &'a CommitSignature) -> Result<Self, Self::Error> {
+		let flag = match signature.vote() {
+			Vote::Absent => super::BLOCK_ID_FLAG_ABSENT,
+			Vote::Commit { .. } => super::BLOCK_ID_FLAG_COMMIT,
+			Vote::Nil { .. } => super::BLOCK_ID_FLAG_NIL,
+		};
+
+		let record = Self::builder()
+			.block_height(signature.block_height().get().try_into()?)
+			.validator_idx(signature.validator_idx().try_into()?)
+			.flag(flag)
+			.maybe_validator_address(signature.vote().validator_address().map(Address::as_bytes))
+			.maybe_time(
+				signature
+					.vote()
+					.time()
+					.map(|time| super::jiff_to_chrono(time).ok_or(InvalidValueError::Time))
+					.transpose()?,
+			)
+			.build();
+
+		Ok(record)
+	}
+}
+
+impl TryFrom<&Evidence> for NewEvidenceRecord {
+	type Error = InvalidValueError;
+
+	fn try_from(evidence: &Evidence) -> Result<Self, Self::Error> {
+		let (kind, validator_power, common_height) = match evidence.kind() {
+			EvidenceKind::DuplicateVote { validator_power } => (
+				super::EVIDENCE_KIND_DUPLICATE_VOTE,
+				Some((*validator_power).try_into()?),
+				None,
+			),
+			EvidenceKind::LightClientAttack { common_height } => (
+				super::EVIDENCE_KIND_LIGHT_CLIENT_ATTACK,
+				None,
+				Some(common_height.get().try_into()?),
+			),
+		};
+
+		let record = Self::builder()
+			.block_height(evidence.block_height().get().try_into()?)
+			.evidence_idx_in_block(evidence.evidence_idx_in_block().try_into()?)
+			.kind(kind)
+			.height(evidence.height().get().try_into()?)
+			.time(super::jiff_to_chrono(evidence.time()).ok_or(InvalidValueError::Time)?)
+			.total_voting_power(evidence.total_voting_power().try_into()?)
+			.maybe_validator_power(validator_power)
+			.maybe_common_height(common_height)
+			.build();
+
+		Ok(record)
+	}
+}
+
+impl<'a> NewValidatorSetRecord<'a> {
+	pub fn try_from_validator(
+		validators_hash: &'a [u8],
+		validator_idx: usize,
+		validator: &'a Validator,
+	) -> Result<Self, InvalidValueError> {
+		let pub_key_type = super::pub_key_type(validator.pub_key()).ok_or(
+			InvalidValueError::Other("unsupported validator public key type".into()),
+		)?;
+
+		let record = Self::builder()
+			.validators_hash(validators_hash)
+			.validator_idx(validator_idx.try_into()?)
+			.address(validator.address().as_bytes())
+			.pub_key_type(pub_key_type)
+			.pub_key(validator.pub_key().to_bytes())
+			.voting_power(validator.voting_power().try_into()?)
+			.build();
+
+		Ok(record)
+	}
+}
+
 fn signer_keys_to_json<I>(keys: I) -> Result<Value, serde_json::Error>
 where
 	I: Iterator<Item = SignerPublicKey>,
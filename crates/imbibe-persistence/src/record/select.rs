@@ -3,13 +3,16 @@ use bon::Builder;
 use chrono::{DateTime, Utc};
 use cosmrs::{
 	Any, Coin,
+	tendermint::PublicKey,
 	tx::{SignatureBytes, SignerPublicKey},
 };
 use diesel::prelude::Queryable;
 use imbibe_domain::{
 	Address, NonEmptyBz, Sha256,
 	block::{AppHash, Block, BlockData, Header},
-	tx::{Codespace, Fees, Memo, Tx},
+	evidence::{Evidence, EvidenceKind},
+	tx::{Codespace, Fees, Info, Log, Memo, Tx},
+	validator::{Validator, Vote},
 };
 use serde_json::Value;
 
@@ -44,7 +47,8 @@ pub struct TxWithDetailsRecord {
 	tx: TxRecord,
 	signatures: Vec<SignatureBytes>,
 	fees: Vec<Coin>,
-	msgs: Vec<Any>,
+	msgs: Vec<MsgRecord>,
+	accounts: Vec<Vec<u8>>,
 }
 
 #[derive(Debug, Queryable)]
@@ -64,6 +68,8 @@ pub struct TxRecord {
 	codespace: Option<String>,
 	data_bz: Option<Vec<u8>>,
 	tx_bz: Vec<u8>,
+	log: Option<String>,
+	info: Option<String>,
 }
 
 #[derive(Debug, Queryable)]
@@ -71,6 +77,11 @@ pub struct SignatureRecord {
 	bz: Vec<u8>,
 }
 
+#[derive(Debug, Queryable)]
+pub struct AccountTxRecord {
+	address: Vec<u8>,
+}
+
 #[derive(Debug, Queryable)]
 pub struct FeeRecord {
 	amount: BigDecimal,
@@ -81,6 +92,40 @@ pub struct FeeRecord {
 pub struct MsgRecord {
 	type_url: String,
 	value: Vec<u8>,
+	json: Option<Value>,
+}
+
+#[derive(Debug, Builder)]
+pub struct EvidenceWithValidatorsRecord {
+	evidence: EvidenceRecord,
+	validators: Vec<Vec<u8>>,
+}
+
+#[derive(Debug, Queryable)]
+pub struct EvidenceRecord {
+	block_height: i64,
+	evidence_idx_in_block: i64,
+	kind: String,
+	height: i64,
+	time: DateTime<Utc>,
+	total_voting_power: i64,
+	validator_power: Option<i64>,
+	common_height: Option<i64>,
+}
+
+#[derive(Debug, Queryable)]
+pub struct ValidatorRecord {
+	address: Vec<u8>,
+	pub_key_type: String,
+	pub_key: Vec<u8>,
+	voting_power: i64,
+}
+
+#[derive(Debug, Queryable)]
+pub struct VoteRecord {
+	flag: i16,
+	validator_address: Option<Vec<u8>>,
+	time: Option<DateTime<Utc>>,
 }
 
 impl TxRecord {
@@ -93,12 +138,28 @@ impl TxRecord {
 	}
 }
 
+impl EvidenceRecord {
+	pub fn block_height(&self) -> i64 {
+		self.block_height
+	}
+
+	pub fn evidence_idx_in_block(&self) -> i64 {
+		self.evidence_idx_in_block
+	}
+}
+
 impl SignatureRecord {
 	pub fn into_bytes(self) -> SignatureBytes {
 		self.bz
 	}
 }
 
+impl AccountTxRecord {
+	pub fn into_bytes(self) -> Vec<u8> {
+		self.address
+	}
+}
+
 impl TryFrom<BlockWithDataRecord> for Block {
 	type Error = InvalidValueError;
 
@@ -171,6 +232,14 @@ impl TryFrom<TxWithDetailsRecord> for Tx {
 
 	fn try_from(record: TxWithDetailsRecord) -> Result<Self, Self::Error> {
 		let txr = record.tx;
+		let (msgs, msgs_json) = record
+			.msgs
+			.into_iter()
+			.map(|MsgRecord { type_url, value, json }| {
+				(Any { type_url, value }, json.map(|json| json.to_string()))
+			})
+			.unzip();
+
 		let tx = Tx::builder()
 			.block_height(u64::try_from(txr.block_height).and_then(|h| h.try_into())?)
 			.tx_idx_in_block(txr.tx_idx_in_block.try_into()?)
@@ -181,7 +250,10 @@ impl TryFrom<TxWithDetailsRecord> for Tx {
 					.map(Sha256::new)
 					.map_err(InvalidValueError::from)?,
 			)
-			.msgs(imbibe_domain::tx::Msgs::new(record.msgs).ok_or(InvalidValueError::Empty)?)
+			.msgs(
+				imbibe_domain::tx::Msgs::with_json(msgs, msgs_json)
+					.ok_or(InvalidValueError::Empty)?,
+			)
 			.maybe_memo(txr.memo.and_then(Memo::new))
 			.maybe_timeout_height(
 				txr.timeout_height
@@ -197,8 +269,17 @@ impl TryFrom<TxWithDetailsRecord> for Tx {
 			.maybe_granter(
 				txr.granter.as_deref().map(TryFrom::try_from).transpose()?.map(Address::new),
 			)
+			.accounts(
+				record
+					.accounts
+					.iter()
+					.map(|bz| bz.as_slice().try_into().map(Address::new))
+					.collect::<Result<_, _>>()?,
+			)
 			.code(u32::try_from(txr.code)?.into())
 			.maybe_codespace(txr.codespace.and_then(Codespace::new))
+			.maybe_log(txr.log.and_then(Log::new))
+			.maybe_info(txr.info.and_then(Info::new))
 			.gas_limit(txr.gas_limit.try_into()?)
 			.gas_wanted(txr.gas_wanted.try_into()?)
 			.gas_used(txr.gas_used.try_into()?)
@@ -210,6 +291,100 @@ impl TryFrom<TxWithDetailsRecord> for Tx {
 	}
 }
 
+impl TryFrom<EvidenceWithValidatorsRecord> for Evidence {
+	type Error = InvalidValueError;
+
+	fn try_from(record: EvidenceWithValidatorsRecord) -> Result<Self, Self::Error> {
+		let er = record.evidence;
+
+		let kind = match (er.kind.as_str(), er.validator_power, er.common_height) {
+			(super::EVIDENCE_KIND_DUPLICATE_VOTE, Some(validator_power), None) => {
+				EvidenceKind::DuplicateVote { validator_power: validator_power.try_into()? }
+			},
+			(super::EVIDENCE_KIND_LIGHT_CLIENT_ATTACK, None, Some(common_height)) => {
+				EvidenceKind::LightClientAttack {
+					common_height: u64::try_from(common_height).and_then(TryFrom::try_from)?,
+				}
+			},
+			(kind, ..) => Err(InvalidValueError::Other(format!(
+				"invalid evidence of kind {kind}"
+			)))?,
+		};
+
+		let evidence = Evidence::builder()
+			.block_height(u64::try_from(er.block_height).and_then(TryFrom::try_from)?)
+			.evidence_idx_in_block(er.evidence_idx_in_block.try_into()?)
+			.kind(kind)
+			.height(u64::try_from(er.height).and_then(TryFrom::try_from)?)
+			.time(super::chrono_to_jiff(&er.time))
+			.total_voting_power(er.total_voting_power.try_into()?)
+			.validators(
+				record
+					.validators
+					.iter()
+					.map(|bz| bz.as_slice().try_into().map(Address::new))
+					.collect::<Result<_, _>>()?,
+			)
+			.build();
+
+		Ok(evidence)
+	}
+}
+
+impl TryFrom<ValidatorRecord> for Validator {
+	type Error = InvalidValueError;
+
+	fn try_from(record: ValidatorRecord) -> Result<Self, Self::Error> {
+		let pub_key = match record.pub_key_type.as_str() {
+			super::PUB_KEY_TYPE_ED25519 => PublicKey::from_raw_ed25519(&record.pub_key),
+			super::PUB_KEY_TYPE_SECP256K1 => PublicKey::from_raw_secp256k1(&record.pub_key),
+			_ => None,
+		}
+		.ok_or(InvalidValueError::Other(format!(
+			"invalid validator public key of type {}",
+			record.pub_key_type
+		)))?;
+
+		let validator = Validator::builder()
+			.address(Address::new(record.address.as_slice().try_into()?))
+			.pub_key(pub_key)
+			.voting_power(record.voting_power.try_into()?)
+			.build();
+
+		Ok(validator)
+	}
+}
+
+impl TryFrom<VoteRecord> for Vote {
+	type Error = InvalidValueError;
+
+	fn try_from(record: VoteRecord) -> Result<Self, Self::Error> {
+		let voted = record
+			.validator_address
+			.zip(record.time)
+			.map(|(address, time)| {
+				address
+					.as_slice()
+					.try_into()
+					.map(|address| (Address::new(address), super::chrono_to_jiff(&time)))
+			})
+			.transpose()?;
+
+		match (record.flag, voted) {
+			(super::BLOCK_ID_FLAG_ABSENT, None) => Ok(Self::Absent),
+			(super::BLOCK_ID_FLAG_COMMIT, Some((validator_address, time))) => {
+				Ok(Self::Commit { validator_address, time })
+			},
+			(super::BLOCK_ID_FLAG_NIL, Some((validator_address, time))) => {
+				Ok(Self::Nil { validator_address, time })
+			},
+			(flag, _) => Err(InvalidValueError::Other(format!(
+				"invalid commit signature with flag {flag}"
+			))),
+		}
+	}
+}
+
 impl TryFrom<&FeeRecord> for Coin {
 	type Error = InvalidValueError;
 
@@ -221,12 +396,6 @@ impl TryFrom<&FeeRecord> for Coin {
 	}
 }
 
-impl From<MsgRecord> for Any {
-	fn from(MsgRecord { type_url, value, .. }: MsgRecord) -> Self {
-		Any { type_url, value }
-	}
-}
-
 fn json_to_signer_keys(json: Value) -> Result<Vec<SignerPublicKey>, InvalidValueError> {
 	match json {
 		Value::Array(keys) => keys
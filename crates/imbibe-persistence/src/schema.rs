@@ -1,5 +1,13 @@
 // @generated automatically by Diesel CLI.
 
+diesel::table! {
+    account_tx (address, block_height, tx_idx_in_block) {
+        address -> Bytea,
+        block_height -> Int8,
+        tx_idx_in_block -> Int8,
+    }
+}
+
 diesel::table! {
     block (height) {
         height -> Int8,
@@ -19,6 +27,68 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    commit_signature (block_height, validator_idx) {
+        block_height -> Int8,
+        validator_idx -> Int8,
+        flag -> Int2,
+        validator_address -> Nullable<Bytea>,
+        time -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    event (block_height, event_idx_in_block) {
+        block_height -> Int8,
+        event_idx_in_block -> Int8,
+        tx_idx_in_block -> Nullable<Int8>,
+        kind -> Text,
+    }
+}
+
+diesel::table! {
+    event_attribute (block_height, event_idx_in_block, attribute_idx_in_event) {
+        block_height -> Int8,
+        event_idx_in_block -> Int8,
+        attribute_idx_in_event -> Int8,
+        key -> Text,
+        value -> Text,
+        index -> Bool,
+    }
+}
+
+diesel::table! {
+    evidence (block_height, evidence_idx_in_block) {
+        block_height -> Int8,
+        evidence_idx_in_block -> Int8,
+        kind -> Text,
+        height -> Int8,
+        time -> Timestamptz,
+        total_voting_power -> Int8,
+        validator_power -> Nullable<Int8>,
+        common_height -> Nullable<Int8>,
+    }
+}
+
+diesel::table! {
+    evidence_validator (block_height, evidence_idx_in_block, validator_idx_in_evidence) {
+        block_height -> Int8,
+        evidence_idx_in_block -> Int8,
+        validator_idx_in_evidence -> Int8,
+        address -> Bytea,
+    }
+}
+
+diesel::table! {
+    failed_block (chain_id, height) {
+        chain_id -> Text,
+        height -> Int8,
+        attempts -> Int4,
+        error -> Text,
+        failed_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     fee (block_height, tx_idx_in_block, fee_idx_in_tx) {
         block_height -> Int8,
@@ -29,6 +99,14 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    indexer_state (chain_id) {
+        chain_id -> Text,
+        indexed_height -> Int8,
+        start_height -> Int8,
+    }
+}
+
 diesel::table! {
     msg (block_height, tx_idx_in_block, msg_idx_in_tx) {
         block_height -> Int8,
@@ -36,6 +114,7 @@ diesel::table! {
         msg_idx_in_tx -> Int8,
         type_url -> Text,
         value -> Bytea,
+        json -> Nullable<Jsonb>,
     }
 }
 
@@ -65,18 +144,45 @@ diesel::table! {
         codespace -> Nullable<Text>,
         data_bz -> Nullable<Bytea>,
         tx_bz -> Bytea,
+        log -> Nullable<Text>,
+        info -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    validator_set (validators_hash, validator_idx) {
+        validators_hash -> Bytea,
+        validator_idx -> Int8,
+        address -> Bytea,
+        pub_key_type -> Text,
+        pub_key -> Bytea,
+        voting_power -> Int8,
     }
 }
 
+diesel::joinable!(account_tx -> block (block_height));
+diesel::joinable!(commit_signature -> block (block_height));
+diesel::joinable!(event -> block (block_height));
+diesel::joinable!(event_attribute -> block (block_height));
+diesel::joinable!(evidence -> block (block_height));
 diesel::joinable!(fee -> block (block_height));
 diesel::joinable!(msg -> block (block_height));
 diesel::joinable!(signature -> block (block_height));
 diesel::joinable!(tx -> block (block_height));
 
 diesel::allow_tables_to_appear_in_same_query!(
+    account_tx,
     block,
+    commit_signature,
+    event,
+    event_attribute,
+    evidence,
+    evidence_validator,
+    failed_block,
     fee,
+    indexer_state,
     msg,
     signature,
     tx,
+    validator_set,
 );
@@ -1,25 +1,53 @@
-use core::num::NonZeroU64;
+use core::{
+	num::{NonZeroU32, NonZeroU64},
+	pin::pin,
+};
+
+use std::{
+	collections::{HashMap, HashSet},
+	time::Instant,
+};
 
 use diesel::{
-	ExpressionMethods, JoinOnDsl, QueryDsl, dsl,
+	BoolExpressionMethods, ExpressionMethods, JoinOnDsl, NullableExpressionMethods,
+	OptionalExtension, QueryDsl, TextExpressionMethods, dsl,
 	prelude::QueryableByName,
-	sql_types::{Array, BigInt, Bytea},
+	sql_types::{Array, BigInt, Bool, Bytea, Integer, Text},
+};
+use diesel_async::{
+	AsyncConnection, AsyncPgConnection, RunQueryDsl, scoped_futures::ScopedFutureExt,
+};
+use futures::{
+	Stream, StreamExt, TryStreamExt,
+	future::{self, Either},
 };
-use diesel_async::{AsyncConnection, RunQueryDsl, scoped_futures::ScopedFutureExt};
-use futures::{Stream, StreamExt, TryStreamExt};
 use imbibe_domain::{
-	Sha256,
-	block::Block,
+	Address, Sha256,
+	block::{Block, TimeBound},
+	event::Event,
+	evidence::Evidence,
+	filter::TxFilter,
+	page::{EvidenceCursor, HeightRange, Order, Page, TxCursor},
 	tx::{Fees, Tx},
+	validator::{BlockSigner, CommitSignature, Validator, ValidatorSet},
 };
+use jiff::Timestamp;
+use tokio_postgres::AsyncMessage;
 
 use crate::{
-	pool::DbConn,
+	pool::{self, DbConn},
 	record::{
-		insert::{NewBlockRecord, NewFeeRecord, NewMsgRecord, NewSignatureRecord, NewTxRecord},
+		BLOCK_ID_FLAG_ABSENT,
+		insert::{
+			NewAccountTxRecord, NewBlockRecord, NewCommitSignatureRecord, NewEventAttributeRecord,
+			NewEventRecord, NewEvidenceRecord, NewEvidenceValidatorRecord, NewFeeRecord,
+			NewMsgRecord, NewSignatureRecord, NewTxRecord, NewValidatorSetRecord,
+		},
+		jiff_to_chrono,
 		select::{
-			BlockWithDataRecord, FeeRecord, MsgRecord, SignatureRecord, TxRecord,
-			TxWithDetailsRecord,
+			AccountTxRecord, BlockWithDataRecord, EvidenceRecord, EvidenceWithValidatorsRecord,
+			FeeRecord, MsgRecord, SignatureRecord, TxRecord, TxWithDetailsRecord, ValidatorRecord,
+			VoteRecord,
 		},
 	},
 	schema,
@@ -27,99 +55,69 @@ use crate::{
 
 use super::{InvalidValueError, StoreError};
 
-#[tracing::instrument(skip_all)]
-pub async fn save_blocks_with_txs<TXS>(
-	conn: &mut DbConn,
-	blocks_with_txs: &[(Block, TXS)],
-) -> Result<(), StoreError>
-where
-	TXS: AsRef<[Tx]>,
-{
-	let mut new_block_records = Vec::with_capacity(blocks_with_txs.len());
-	let mut new_tx_records = vec![];
-	let mut new_signature_records = vec![];
-	let mut new_fee_records = vec![];
-	let mut new_msg_records = vec![];
+const MAX_BIND_PARAMS: usize = u16::MAX as usize;
 
-	for (block, txs) in blocks_with_txs {
-		new_block_records.push(NewBlockRecord::try_from(block)?);
+// channel on which the heights of the live indexed blocks are notified
+const INDEXED_BLOCK_CHANNEL: &str = "imbibe_indexed_block";
 
-		process_new_records_from_txs(
-			txs.as_ref(),
-			&mut new_tx_records,
-			&mut new_signature_records,
-			&mut new_fee_records,
-			&mut new_msg_records,
-		)?;
-	}
+type BlockWithTxs<'a> = (
+	&'a Block,
+	&'a [Tx],
+	&'a [Event],
+	&'a [CommitSignature],
+	&'a [Evidence],
+);
 
-	conn.transaction(|conn| {
-		async move {
-			diesel::insert_into(schema::block::table)
-				.values(new_block_records)
-				.execute(conn)
-				.await?;
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum WriteMode {
+	/// Fails if any of the blocks is already stored.
+	#[default]
+	Strict,
 
-			diesel::insert_into(schema::tx::table).values(new_tx_records).execute(conn).await?;
-			diesel::insert_into(schema::signature::table)
-				.values(new_signature_records)
-				.execute(conn)
-				.await?;
-			diesel::insert_into(schema::fee::table).values(new_fee_records).execute(conn).await?;
-			diesel::insert_into(schema::msg::table).values(new_msg_records).execute(conn).await?;
+	/// Skips the blocks that are already stored with the same block hash, and fails only if a
+	/// block is stored with a different block hash.
+	Idempotent,
+}
 
-			Result::<_, StoreError>::Ok(())
-		}
-		.scope_boxed()
-	})
-	.await?;
+#[tracing::instrument(skip(conn, blocks_with_txs))]
+pub async fn save_blocks_with_txs<TXS, EVS, SIGS, EVDS>(
+	conn: &mut DbConn,
+	blocks_with_txs: &[(Block, TXS, EVS, SIGS, EVDS)],
+	mode: WriteMode,
+) -> Result<(), StoreError>
+where
+	TXS: AsRef<[Tx]>,
+	EVS: AsRef<[Event]>,
+	SIGS: AsRef<[CommitSignature]>,
+	EVDS: AsRef<[Evidence]>,
+{
+	let blocks_with_txs = blocks_with_txs
+		.iter()
+		.map(|(block, txs, events, signatures, evidence)| {
+			(
+				block,
+				txs.as_ref(),
+				events.as_ref(),
+				signatures.as_ref(),
+				evidence.as_ref(),
+			)
+		})
+		.collect();
 
-	Ok(())
+	save_blocks(conn, blocks_with_txs, mode).await
 }
 
-#[tracing::instrument(skip_all)]
+#[tracing::instrument(skip(conn, block, txs, events, signatures, evidence))]
 pub async fn save_block_with_txs(
 	conn: &mut DbConn,
 	block: &Block,
 	txs: &[Tx],
+	events: &[Event],
+	signatures: &[CommitSignature],
+	evidence: &[Evidence],
+	mode: WriteMode,
 ) -> Result<(), StoreError> {
-	let new_block_record = NewBlockRecord::try_from(block)?;
-
-	let mut new_tx_records = Vec::with_capacity(txs.len());
-	let mut new_signature_records = vec![];
-	let mut new_fee_records = vec![];
-	let mut new_msg_records = vec![];
-
-	process_new_records_from_txs(
-		txs,
-		&mut new_tx_records,
-		&mut new_signature_records,
-		&mut new_fee_records,
-		&mut new_msg_records,
-	)?;
-
-	conn.transaction(|conn| {
-		async move {
-			diesel::insert_into(schema::block::table)
-				.values(new_block_record)
-				.execute(conn)
-				.await?;
-
-			diesel::insert_into(schema::tx::table).values(new_tx_records).execute(conn).await?;
-			diesel::insert_into(schema::signature::table)
-				.values(new_signature_records)
-				.execute(conn)
-				.await?;
-			diesel::insert_into(schema::fee::table).values(new_fee_records).execute(conn).await?;
-			diesel::insert_into(schema::msg::table).values(new_msg_records).execute(conn).await?;
-
-			Result::<_, StoreError>::Ok(())
-		}
-		.scope_boxed()
-	})
-	.await?;
-
-	Ok(())
+	save_blocks(conn, vec![(block, txs, events, signatures, evidence)], mode).await
 }
 
 #[tracing::instrument(skip(conn))]
@@ -155,6 +153,533 @@ pub async fn fetch_missing_block_heights(
 	Ok(stream)
 }
 
+// moves the start of the watermark upto the given height when the blocks below it are not indexed,
+// returning the watermark; the start is only ever raised, hence a height below the stored start is
+// ignored, as lowering it would drop the watermark below the blocks already indexed
+#[tracing::instrument(skip(conn))]
+pub async fn raise_start_height(
+	conn: &mut DbConn,
+	chain_id: &str,
+	start_height: NonZeroU64,
+) -> Result<u64, StoreError> {
+	let start_height = i64::try_from(start_height.get()).map_err(InvalidValueError::from)?;
+
+	diesel::insert_into(schema::indexer_state::table)
+		.values((
+			schema::indexer_state::chain_id.eq(chain_id),
+			schema::indexer_state::indexed_height.eq(start_height - 1),
+			schema::indexer_state::start_height.eq(start_height),
+		))
+		.on_conflict(schema::indexer_state::chain_id)
+		.do_update()
+		.set((
+			schema::indexer_state::indexed_height.eq(dsl::sql::<BigInt>(
+				"GREATEST(indexer_state.indexed_height, excluded.indexed_height)",
+			)),
+			schema::indexer_state::start_height.eq(dsl::sql::<BigInt>(
+				"CASE WHEN indexer_state.indexed_height < excluded.indexed_height \
+				 THEN excluded.start_height ELSE indexer_state.start_height END",
+			)),
+		))
+		.returning(schema::indexer_state::indexed_height)
+		.get_result::<i64>(conn)
+		.await?
+		.try_into()
+		.map_err(InvalidValueError::from)
+		.map_err(From::from)
+}
+
+#[tracing::instrument(skip(conn))]
+pub async fn advance_indexed_height(
+	conn: &mut DbConn,
+	chain_id: &str,
+) -> Result<Option<NonZeroU64>, StoreError> {
+	#[derive(QueryableByName)]
+	struct IndexedHeight {
+		#[diesel(sql_type = BigInt)]
+		indexed_height: i64,
+	}
+
+	// moves the watermark to the end of the run of contiguous blocks of the chain right after it,
+	// if any
+	const SQL: &str = r#"
+		WITH next AS (
+			SELECT b.height
+			FROM indexer_state s
+			JOIN block b ON b.chain_id = s.chain_id AND b.height > s.indexed_height
+			WHERE s.chain_id = $1
+				AND EXISTS (
+					SELECT 1 FROM block f
+					WHERE f.chain_id = s.chain_id AND f.height = s.indexed_height + 1
+				)
+				AND NOT EXISTS (
+					SELECT 1 FROM block n
+					WHERE n.chain_id = s.chain_id AND n.height = b.height + 1
+				)
+			ORDER BY b.height ASC
+			LIMIT 1
+		)
+		UPDATE indexer_state
+		SET indexed_height = next.height
+		FROM next
+		WHERE indexer_state.chain_id = $1 AND indexer_state.indexed_height < next.height
+		RETURNING indexer_state.indexed_height
+	"#;
+
+	diesel::sql_query(SQL)
+		.bind::<Text, _>(chain_id)
+		.get_result::<IndexedHeight>(conn)
+		.await
+		.optional()?
+		.map(|h| u64::try_from(h.indexed_height).map(NonZeroU64::new))
+		.transpose()
+		.map(Option::flatten)
+		.map_err(InvalidValueError::from)
+		.map_err(From::from)
+}
+
+#[tracing::instrument(skip(conn))]
+pub async fn fetch_start_height(
+	conn: &mut DbConn,
+	chain_id: &str,
+) -> Result<Option<NonZeroU64>, StoreError> {
+	schema::indexer_state::table
+		.select(schema::indexer_state::start_height)
+		.filter(schema::indexer_state::chain_id.eq(chain_id))
+		.first::<i64>(conn)
+		.await
+		.optional()?
+		.map(|h| u64::try_from(h).and_then(NonZeroU64::try_from))
+		.transpose()
+		.map_err(InvalidValueError::from)
+		.map_err(From::from)
+}
+
+#[tracing::instrument(skip(conn))]
+pub async fn fetch_indexed_height(
+	conn: &mut DbConn,
+	chain_id: &str,
+) -> Result<Option<NonZeroU64>, StoreError> {
+	schema::indexer_state::table
+		.select(schema::indexer_state::indexed_height)
+		.filter(schema::indexer_state::chain_id.eq(chain_id))
+		.first::<i64>(conn)
+		.await
+		.optional()?
+		.map(|h| u64::try_from(h).map(NonZeroU64::new))
+		.transpose()
+		.map(Option::flatten)
+		.map_err(InvalidValueError::from)
+		.map_err(From::from)
+}
+
+#[tracing::instrument(skip(conn))]
+pub async fn notify_indexed_block(conn: &mut DbConn, height: NonZeroU64) -> Result<(), StoreError> {
+	diesel::sql_query("SELECT pg_notify($1, $2)")
+		.bind::<Text, _>(INDEXED_BLOCK_CHANNEL)
+		.bind::<Text, _>(height.to_string())
+		.execute(conn)
+		.await?;
+
+	Ok(())
+}
+
+// listens on a dedicated connection, as the pooled connections do not surface the notifications;
+// the stream ends when the connection is closed
+#[tracing::instrument(skip_all)]
+pub async fn listen_indexed_blocks(
+	db_url: &str,
+) -> Result<impl Stream<Item = Result<NonZeroU64, StoreError>> + use<>, StoreError> {
+	if pool::requires_tls(db_url) {
+		return Err(StoreError::ListenTlsRequired);
+	}
+
+	let (client, mut connection) = tokio_postgres::connect(db_url, tokio_postgres::NoTls).await?;
+	let mut messages = futures::stream::poll_fn(move |cx| connection.poll_message(cx));
+
+	// the connection only makes progress while being polled, hence drive it until listening
+	{
+		let sql = format!("LISTEN {INDEXED_BLOCK_CHANNEL}");
+		let mut listen = pin!(client.batch_execute(&sql));
+
+		loop {
+			match future::select(listen.as_mut(), messages.next()).await {
+				Either::Left((res, _)) => break res?,
+				Either::Right((Some(Err(e)), _)) => return Err(e.into()),
+				Either::Right((Some(Ok(_)), _)) => continue,
+				Either::Right((None, _)) => return Err(StoreError::ListenClosed),
+			}
+		}
+	}
+
+	let heights = messages.filter_map(move |message| {
+		// the connection is closed once the client is dropped
+		let _client = &client;
+
+		future::ready(match message {
+			Ok(AsyncMessage::Notification(n)) => Some(
+				n.payload()
+					.parse::<NonZeroU64>()
+					.map_err(|_| InvalidValueError::Other(n.payload().into()).into()),
+			),
+			Ok(_) => None,
+			Err(e) => Some(Err(e.into())),
+		})
+	});
+
+	Ok(heights)
+}
+
+#[tracing::instrument(skip(conn))]
+pub async fn save_failed_block(
+	conn: &mut DbConn,
+	chain_id: &str,
+	height: u64,
+	attempts: NonZeroU32,
+	error: &str,
+) -> Result<(), StoreError> {
+	diesel::insert_into(schema::failed_block::table)
+		.values((
+			schema::failed_block::chain_id.eq(chain_id),
+			schema::failed_block::height
+				.eq(i64::try_from(height).map_err(InvalidValueError::from)?),
+			schema::failed_block::attempts
+				.eq(i32::try_from(attempts.get()).map_err(InvalidValueError::from)?),
+			schema::failed_block::error.eq(error),
+		))
+		.on_conflict((schema::failed_block::chain_id, schema::failed_block::height))
+		.do_update()
+		.set((
+			schema::failed_block::attempts
+				.eq(schema::failed_block::attempts + dsl::sql::<Integer>("excluded.attempts")),
+			schema::failed_block::error.eq(dsl::sql::<Text>("excluded.error")),
+			schema::failed_block::failed_at.eq(dsl::now),
+		))
+		.execute(conn)
+		.await?;
+
+	Ok(())
+}
+
+#[tracing::instrument(skip(conn, heights))]
+pub async fn remove_failed_blocks(
+	conn: &mut DbConn,
+	chain_id: &str,
+	heights: &[u64],
+) -> Result<usize, StoreError> {
+	let heights = heights
+		.iter()
+		.copied()
+		.map(i64::try_from)
+		.collect::<Result<Vec<_>, _>>()
+		.map_err(InvalidValueError::from)?;
+
+	diesel::delete(schema::failed_block::table)
+		.filter(schema::failed_block::chain_id.eq(chain_id))
+		.filter(schema::failed_block::height.eq_any(heights))
+		.execute(conn)
+		.await
+		.map_err(From::from)
+}
+
+#[tracing::instrument(skip(conn))]
+pub async fn fetch_block_hash(
+	conn: &mut DbConn,
+	height: NonZeroU64,
+) -> Result<Option<Sha256>, StoreError> {
+	schema::block::table
+		.select(schema::block::block_hash)
+		.filter(schema::block::height.eq(to_db_height(height)?))
+		.first::<Vec<u8>>(conn)
+		.await
+		.optional()?
+		.map(|hash| <[u8; Sha256::LEN]>::try_from(hash.as_slice()).map(Sha256::new))
+		.transpose()
+		.map_err(InvalidValueError::from)
+		.map_err(From::from)
+}
+
+// removes the blocks from the height upwards along with their txs and events, and lowers the
+// indexed height of the chain below the height; returns the number of blocks removed
+#[tracing::instrument(skip(conn))]
+pub async fn remove_blocks_from(
+	conn: &mut DbConn,
+	chain_id: &str,
+	height: NonZeroU64,
+) -> Result<usize, StoreError> {
+	let height = to_db_height(height)?;
+
+	conn.transaction(|conn| {
+		async move {
+			diesel::delete(schema::event_attribute::table)
+				.filter(schema::event_attribute::block_height.ge(height))
+				.execute(conn)
+				.await?;
+			diesel::delete(schema::event::table)
+				.filter(schema::event::block_height.ge(height))
+				.execute(conn)
+				.await?;
+			diesel::delete(schema::account_tx::table)
+				.filter(schema::account_tx::block_height.ge(height))
+				.execute(conn)
+				.await?;
+			diesel::delete(schema::msg::table)
+				.filter(schema::msg::block_height.ge(height))
+				.execute(conn)
+				.await?;
+			diesel::delete(schema::fee::table)
+				.filter(schema::fee::block_height.ge(height))
+				.execute(conn)
+				.await?;
+			diesel::delete(schema::signature::table)
+				.filter(schema::signature::block_height.ge(height))
+				.execute(conn)
+				.await?;
+			diesel::delete(schema::tx::table)
+				.filter(schema::tx::block_height.ge(height))
+				.execute(conn)
+				.await?;
+			diesel::delete(schema::commit_signature::table)
+				.filter(schema::commit_signature::block_height.ge(height))
+				.execute(conn)
+				.await?;
+			diesel::delete(schema::evidence_validator::table)
+				.filter(schema::evidence_validator::block_height.ge(height))
+				.execute(conn)
+				.await?;
+			diesel::delete(schema::evidence::table)
+				.filter(schema::evidence::block_height.ge(height))
+				.execute(conn)
+				.await?;
+
+			let removed = diesel::delete(schema::block::table)
+				.filter(schema::block::height.ge(height))
+				.execute(conn)
+				.await?;
+
+			diesel::update(schema::indexer_state::table)
+				.filter(schema::indexer_state::chain_id.eq(chain_id))
+				.filter(schema::indexer_state::indexed_height.ge(height))
+				.set(schema::indexer_state::indexed_height.eq(height - 1))
+				.execute(conn)
+				.await?;
+
+			Result::<_, StoreError>::Ok(removed)
+		}
+		.scope_boxed()
+	})
+	.await
+}
+
+#[tracing::instrument(skip(conn))]
+pub async fn validator_set_exists(
+	conn: &mut DbConn,
+	validators_hash: &Sha256,
+) -> Result<bool, StoreError> {
+	diesel::select(dsl::exists(schema::validator_set::table.filter(
+		schema::validator_set::validators_hash.eq(validators_hash.get().as_slice()),
+	)))
+	.get_result(conn)
+	.await
+	.map_err(From::from)
+}
+
+// the hashes among the given ones whose validator sets are stored, checked at once for a batch of
+// blocks rather than block by block
+#[tracing::instrument(skip_all, fields(validators_hashes = validators_hashes.len()))]
+pub async fn fetch_stored_validators_hashes(
+	conn: &mut DbConn,
+	validators_hashes: &[Sha256],
+) -> Result<Vec<Sha256>, StoreError> {
+	let validators_hashes = validators_hashes.iter().map(|hash| hash.get().as_slice());
+
+	schema::validator_set::table
+		.select(schema::validator_set::validators_hash)
+		.filter(schema::validator_set::validators_hash.eq_any(validators_hashes))
+		.distinct()
+		.load::<Vec<u8>>(conn)
+		.await?
+		.into_iter()
+		.map(|hash| <[u8; Sha256::LEN]>::try_from(hash.as_slice()).map(Sha256::new))
+		.collect::<Result<_, _>>()
+		.map_err(InvalidValueError::from)
+		.map_err(From::from)
+}
+
+// saves the validator set unless it is already stored, as the validator sets are shared by the
+// blocks and may be saved concurrently
+#[tracing::instrument(skip(conn, validator_set), fields(validators_hash = %validator_set.validators_hash()))]
+pub async fn save_validator_set(
+	conn: &mut DbConn,
+	validator_set: &ValidatorSet,
+) -> Result<(), StoreError> {
+	let validators_hash = validator_set.validators_hash().get().as_slice();
+	let records = validator_set
+		.validators()
+		.iter()
+		.enumerate()
+		.map(|(idx, validator)| {
+			NewValidatorSetRecord::try_from_validator(validators_hash, idx, validator)
+		})
+		.collect::<Result<Vec<_>, _>>()?;
+
+	conn.transaction(|conn| {
+		async move {
+			for chunk in records.chunks(MAX_BIND_PARAMS / 6) {
+				diesel::insert_into(schema::validator_set::table)
+					.values(chunk)
+					.on_conflict_do_nothing()
+					.execute(conn)
+					.await?;
+			}
+
+			Result::<_, StoreError>::Ok(())
+		}
+		.scope_boxed()
+	})
+	.await
+}
+
+// validator set in charge of the block at the height
+#[tracing::instrument(skip(conn))]
+pub async fn fetch_validator_set(
+	conn: &mut DbConn,
+	height: NonZeroU64,
+) -> Result<ValidatorSet, StoreError> {
+	let validators_hash = schema::block::table
+		.select(schema::block::validators_hash)
+		.filter(schema::block::height.eq(to_db_height(height)?))
+		.first::<Vec<u8>>(conn)
+		.await?;
+
+	let validators = fetch_validators(conn, &validators_hash).await?;
+	if validators.is_empty() {
+		return Err(diesel::result::Error::NotFound.into());
+	}
+
+	let validator_set = ValidatorSet::builder()
+		.validators_hash(Sha256::new(
+			validators_hash.as_slice().try_into().map_err(InvalidValueError::from)?,
+		))
+		.validators(validators)
+		.build();
+
+	Ok(validator_set)
+}
+
+// validators of the block at the height along with their votes for it, which are included in
+// the block above it
+#[tracing::instrument(skip(conn))]
+pub async fn fetch_block_signers(
+	conn: &mut DbConn,
+	height: NonZeroU64,
+) -> Result<Vec<BlockSigner>, StoreError> {
+	let validator_set = fetch_validator_set(conn, height).await?;
+	let committed_in = to_db_height(height)?
+		.checked_add(1)
+		.ok_or(InvalidValueError::Other("height overflow".into()))?;
+
+	let votes = schema::commit_signature::table
+		.select((
+			schema::commit_signature::flag,
+			schema::commit_signature::validator_address,
+			schema::commit_signature::time,
+		))
+		.filter(schema::commit_signature::block_height.eq(committed_in))
+		.order(schema::commit_signature::validator_idx.asc())
+		.load::<VoteRecord>(conn)
+		.await?;
+
+	// the commit is yet to be indexed
+	if votes.is_empty() {
+		return Err(diesel::result::Error::NotFound.into());
+	}
+
+	if votes.len() != validator_set.validators().len() {
+		return Err(InvalidValueError::Other(format!(
+			"commit of block {height} has {} signatures for {} validators",
+			votes.len(),
+			validator_set.validators().len()
+		))
+		.into());
+	}
+
+	validator_set
+		.validators()
+		.iter()
+		.cloned()
+		.zip(votes)
+		.map(|(validator, vote)| {
+			Ok(BlockSigner::builder().validator(validator).vote(vote.try_into()?).build())
+		})
+		.collect()
+}
+
+// heights of the blocks in the range for which the validator was in the validator set but its
+// vote is absent from the commit, continuing after the cursor in the given order
+#[tracing::instrument(skip(conn))]
+pub async fn fetch_missed_blocks(
+	conn: &mut DbConn,
+	address: &Address,
+	range: HeightRange,
+	after: Option<NonZeroU64>,
+	order: Order,
+	limit: NonZeroU64,
+) -> Result<Page<NonZeroU64, NonZeroU64>, StoreError> {
+	let mut query = schema::commit_signature::table
+		.inner_join(
+			schema::block::table
+				.on(schema::block::height.eq(schema::commit_signature::block_height - 1)),
+		)
+		.inner_join(schema::validator_set::table.on(
+			schema::validator_set::validators_hash.eq(schema::block::validators_hash).and(
+				schema::validator_set::validator_idx.eq(schema::commit_signature::validator_idx),
+			),
+		))
+		.select(schema::block::height)
+		.filter(schema::validator_set::address.eq(address.as_bytes()))
+		.filter(schema::commit_signature::flag.eq(BLOCK_ID_FLAG_ABSENT))
+		// fetching one more than the limit tells whether there is a next page
+		.limit(i64::try_from(limit.get()).map_err(InvalidValueError::from)?.saturating_add(1))
+		.into_boxed();
+
+	if let Some(lo) = range.lo() {
+		query = query.filter(schema::block::height.ge(to_db_height(lo)?));
+	}
+
+	if let Some(hi) = range.hi() {
+		query = query.filter(schema::block::height.le(to_db_height(hi)?));
+	}
+
+	query = match order {
+		Order::Asc => query.order(schema::block::height.asc()),
+		Order::Desc => query.order(schema::block::height.desc()),
+	};
+
+	if let Some(after) = after {
+		let after = to_db_height(after)?;
+		query = match order {
+			Order::Asc => query.filter(schema::block::height.gt(after)),
+			Order::Desc => query.filter(schema::block::height.lt(after)),
+		};
+	}
+
+	let mut heights = query
+		.load::<i64>(conn)
+		.await?
+		.into_iter()
+		.map(|h| u64::try_from(h).and_then(NonZeroU64::try_from))
+		.collect::<Result<Vec<_>, _>>()
+		.map_err(InvalidValueError::from)?;
+
+	let has_next = heights.len() as u64 > limit.get();
+	heights.truncate(usize::try_from(limit.get()).unwrap_or(usize::MAX));
+
+	let next_cursor = has_next.then(|| heights.last().copied()).flatten();
+
+	Ok(Page::builder().items(heights).maybe_next_cursor(next_cursor).build())
+}
+
 #[tracing::instrument(skip(conn))]
 pub async fn fetch_block_by_height(
 	conn: &mut DbConn,
@@ -197,6 +722,31 @@ pub async fn fetch_block_by_block_hash(
 		.map_err(From::from)
 }
 
+#[tracing::instrument(skip(conn))]
+pub async fn fetch_block_by_time(
+	conn: &mut DbConn,
+	time: &Timestamp,
+	bound: TimeBound,
+) -> Result<Block, StoreError> {
+	let time = jiff_to_chrono(time).ok_or(InvalidValueError::Time)?;
+
+	let query = schema::block::table.select(schema::block::height).into_boxed();
+	let query = match bound {
+		TimeBound::AtOrBefore => query
+			.filter(schema::block::time.le(time))
+			.order((schema::block::time.desc(), schema::block::height.desc())),
+		TimeBound::AtOrAfter => query
+			.filter(schema::block::time.ge(time))
+			.order((schema::block::time.asc(), schema::block::height.asc())),
+	};
+
+	let height = query.first::<i64>(conn).await?;
+	let height =
+		u64::try_from(height).and_then(NonZeroU64::try_from).map_err(InvalidValueError::from)?;
+
+	fetch_block_by_height(conn, height).await
+}
+
 #[tracing::instrument(skip(conn))]
 pub async fn fetch_tx_by_block_height_and_tx_idx_in_block(
 	conn: &mut DbConn,
@@ -213,18 +763,7 @@ pub async fn fetch_tx_by_block_height_and_tx_idx_in_block(
 		.first(conn)
 		.await?;
 
-	let signatures = fetch_signatures(conn, height, tx_idx_in_block).await?;
-	let fee = fetch_fee(conn, height, tx_idx_in_block).await?;
-	let msgs = fetch_msgs(conn, height, tx_idx_in_block).await?;
-
-	TxWithDetailsRecord::builder()
-		.tx(tx)
-		.signatures(signatures.into_iter().map(SignatureRecord::into_bytes).collect())
-		.msgs(msgs.into_iter().map(From::from).collect())
-		.fees(fee.iter().map(TryFrom::try_from).collect::<Result<_, _>>()?)
-		.build()
-		.try_into()
-		.map_err(From::from)
+	fetch_tx_details(conn, tx).await
 }
 
 #[tracing::instrument(skip(conn))]
@@ -235,21 +774,183 @@ pub async fn fetch_tx_by_tx_hash(conn: &mut DbConn, tx_hash: &Sha256) -> Result<
 		.first::<TxRecord>(conn)
 		.await?;
 
-	let height = tx.block_height();
-	let tx_idx_in_block = tx.tx_idx_in_block();
+	fetch_tx_details(conn, tx).await
+}
 
-	let signatures = fetch_signatures(conn, height, tx_idx_in_block).await?;
-	let fee = fetch_fee(conn, height, tx_idx_in_block).await?;
-	let msgs = fetch_msgs(conn, height, tx_idx_in_block).await?;
+async fn save_blocks(
+	conn: &mut DbConn,
+	blocks_with_txs: Vec<BlockWithTxs<'_>>,
+	mode: WriteMode,
+) -> Result<(), StoreError> {
+	let started = Instant::now();
+	let saved = insert_blocks_with_txs(conn, blocks_with_txs, mode).await;
 
-	TxWithDetailsRecord::builder()
-		.tx(tx)
-		.signatures(signatures.into_iter().map(SignatureRecord::into_bytes).collect())
-		.msgs(msgs.into_iter().map(From::from).collect())
-		.fees(fee.iter().map(TryFrom::try_from).collect::<Result<_, _>>()?)
-		.build()
-		.try_into()
-		.map_err(From::from)
+	crate::metrics::record_save_blocks(started.elapsed(), saved.is_ok());
+
+	saved
+}
+
+async fn insert_blocks_with_txs(
+	conn: &mut DbConn,
+	blocks_with_txs: Vec<BlockWithTxs<'_>>,
+	mode: WriteMode,
+) -> Result<(), StoreError> {
+	let new_block_records = blocks_with_txs
+		.iter()
+		.map(|(block, ..)| NewBlockRecord::try_from(*block))
+		.collect::<Result<Vec<_>, _>>()?;
+
+	conn.transaction(|conn| {
+		async move {
+			let stored_heights =
+				insert_blocks(conn, &blocks_with_txs, &new_block_records, mode).await?;
+
+			let mut new_tx_records = vec![];
+			let mut new_signature_records = vec![];
+			let mut new_fee_records = vec![];
+			let mut new_msg_records = vec![];
+			let mut new_account_tx_records = vec![];
+			let mut new_event_records = vec![];
+			let mut new_event_attribute_records = vec![];
+			let mut new_commit_signature_records = vec![];
+			let mut new_evidence_records = vec![];
+			let mut new_evidence_validator_records = vec![];
+
+			for (block, txs, events, signatures, evidence) in &blocks_with_txs {
+				if stored_heights.contains(&block.header().height()) {
+					continue;
+				}
+
+				process_new_records_from_txs(
+					txs,
+					&mut new_tx_records,
+					&mut new_signature_records,
+					&mut new_fee_records,
+					&mut new_msg_records,
+					&mut new_account_tx_records,
+				)?;
+
+				process_new_records_from_events(
+					events,
+					&mut new_event_records,
+					&mut new_event_attribute_records,
+				)?;
+
+				for signature in *signatures {
+					new_commit_signature_records
+						.push(NewCommitSignatureRecord::try_from(signature)?);
+				}
+
+				process_new_records_from_evidence(
+					evidence,
+					&mut new_evidence_records,
+					&mut new_evidence_validator_records,
+				)?;
+			}
+
+			diesel::insert_into(schema::tx::table).values(new_tx_records).execute(conn).await?;
+			diesel::insert_into(schema::signature::table)
+				.values(new_signature_records)
+				.execute(conn)
+				.await?;
+			diesel::insert_into(schema::fee::table).values(new_fee_records).execute(conn).await?;
+			diesel::insert_into(schema::msg::table).values(new_msg_records).execute(conn).await?;
+
+			for chunk in new_account_tx_records.chunks(MAX_BIND_PARAMS / 3) {
+				diesel::insert_into(schema::account_tx::table).values(chunk).execute(conn).await?;
+			}
+
+			insert_events(conn, &new_event_records, &new_event_attribute_records).await?;
+
+			for chunk in new_commit_signature_records.chunks(MAX_BIND_PARAMS / 5) {
+				diesel::insert_into(schema::commit_signature::table)
+					.values(chunk)
+					.execute(conn)
+					.await?;
+			}
+
+			diesel::insert_into(schema::evidence::table)
+				.values(new_evidence_records)
+				.execute(conn)
+				.await?;
+			diesel::insert_into(schema::evidence_validator::table)
+				.values(new_evidence_validator_records)
+				.execute(conn)
+				.await?;
+
+			Result::<_, StoreError>::Ok(())
+		}
+		.scope_boxed()
+	})
+	.await
+}
+
+// returns the heights of the blocks that were already stored, and hence not inserted
+async fn insert_blocks(
+	conn: &mut AsyncPgConnection,
+	blocks_with_txs: &[BlockWithTxs<'_>],
+	new_block_records: &[NewBlockRecord<'_>],
+	mode: WriteMode,
+) -> Result<HashSet<u64>, StoreError> {
+	if mode == WriteMode::Strict {
+		diesel::insert_into(schema::block::table).values(new_block_records).execute(conn).await?;
+
+		return Ok(HashSet::new());
+	}
+
+	let inserted_heights: HashSet<i64> = diesel::insert_into(schema::block::table)
+		.values(new_block_records)
+		.on_conflict(schema::block::height)
+		.do_nothing()
+		.returning(schema::block::height)
+		.get_results(conn)
+		.await?
+		.into_iter()
+		.collect();
+
+	let mut skipped_blocks = HashMap::new();
+	for (block, ..) in blocks_with_txs {
+		let height = block.header().height();
+		if !inserted_heights.contains(&height.try_into().map_err(InvalidValueError::from)?) {
+			skipped_blocks.insert(height, block.hash());
+		}
+	}
+
+	if skipped_blocks.is_empty() {
+		return Ok(HashSet::new());
+	}
+
+	let skipped_heights = skipped_blocks
+		.keys()
+		.copied()
+		.map(i64::try_from)
+		.collect::<Result<Vec<_>, _>>()
+		.map_err(InvalidValueError::from)?;
+
+	let stored_hashes = schema::block::table
+		.select((schema::block::height, schema::block::block_hash))
+		.filter(schema::block::height.eq_any(skipped_heights))
+		.load::<(i64, Vec<u8>)>(conn)
+		.await?;
+
+	for (height, stored_hash) in stored_hashes {
+		let height = u64::try_from(height).map_err(InvalidValueError::from)?;
+		let stored_hash =
+			Sha256::new(stored_hash.as_slice().try_into().map_err(InvalidValueError::from)?);
+
+		let hash = skipped_blocks[&height];
+		if stored_hash.get() != hash.get() {
+			return Err(StoreError::BlockHashMismatch {
+				height,
+				stored: stored_hash,
+				new: hash.clone(),
+			});
+		}
+
+		tracing::info!("skipping block {height} as it is already stored");
+	}
+
+	Ok(skipped_blocks.into_keys().collect())
 }
 
 fn process_new_records_from_txs<'a>(
@@ -258,6 +959,7 @@ fn process_new_records_from_txs<'a>(
 	new_signature_records: &mut Vec<NewSignatureRecord<'a>>,
 	new_fee_records: &mut Vec<NewFeeRecord<'a>>,
 	new_msg_records: &mut Vec<NewMsgRecord<'a>>,
+	new_account_tx_records: &mut Vec<NewAccountTxRecord<'a>>,
 ) -> Result<(), InvalidValueError> {
 	for tx in txs {
 		new_tx_records.push(tx.try_into()?);
@@ -288,22 +990,650 @@ fn process_new_records_from_txs<'a>(
 			new_fee_records.push(fee_record);
 		}
 
-		for (idx, msg) in tx.msgs().get().iter().enumerate() {
+		for (idx, (msg, json)) in tx.msgs().get().iter().zip(tx.msgs().json()).enumerate() {
 			let msg_record = NewMsgRecord::builder()
 				.block_height(block_height)
 				.tx_idx_in_block(tx_idx_in_block)
 				.msg_idx_in_tx(idx.try_into()?)
 				.type_url(&msg.type_url)
 				.value(&msg.value)
+				.maybe_json(json.as_deref().map(serde_json::from_str).transpose()?)
 				.build();
 
 			new_msg_records.push(msg_record);
 		}
+
+		for address in tx.accounts() {
+			let account_tx_record = NewAccountTxRecord::builder()
+				.address(address.as_bytes())
+				.block_height(block_height)
+				.tx_idx_in_block(tx_idx_in_block)
+				.build();
+
+			new_account_tx_records.push(account_tx_record);
+		}
+	}
+
+	Ok(())
+}
+
+fn process_new_records_from_events<'a>(
+	events: &'a [Event],
+	new_event_records: &mut Vec<NewEventRecord<'a>>,
+	new_event_attribute_records: &mut Vec<NewEventAttributeRecord<'a>>,
+) -> Result<(), InvalidValueError> {
+	for event in events {
+		new_event_records.push(event.try_into()?);
+
+		let block_height = event.block_height().get().try_into()?;
+		let event_idx_in_block = event.event_idx_in_block().try_into()?;
+
+		for (idx, attribute) in event.attributes().iter().enumerate() {
+			let event_attribute_record = NewEventAttributeRecord::builder()
+				.block_height(block_height)
+				.event_idx_in_block(event_idx_in_block)
+				.attribute_idx_in_event(idx.try_into()?)
+				.key(attribute.key())
+				.value(attribute.value())
+				.index(attribute.index())
+				.build();
+
+			new_event_attribute_records.push(event_attribute_record);
+		}
+	}
+
+	Ok(())
+}
+
+fn process_new_records_from_evidence<'a>(
+	evidence: &'a [Evidence],
+	new_evidence_records: &mut Vec<NewEvidenceRecord>,
+	new_evidence_validator_records: &mut Vec<NewEvidenceValidatorRecord<'a>>,
+) -> Result<(), InvalidValueError> {
+	for evidence in evidence {
+		new_evidence_records.push(evidence.try_into()?);
+
+		let block_height = evidence.block_height().get().try_into()?;
+		let evidence_idx_in_block = evidence.evidence_idx_in_block().try_into()?;
+
+		for (idx, address) in evidence.validators().iter().enumerate() {
+			let evidence_validator_record = NewEvidenceValidatorRecord::builder()
+				.block_height(block_height)
+				.evidence_idx_in_block(evidence_idx_in_block)
+				.validator_idx_in_evidence(idx.try_into()?)
+				.address(address.as_bytes())
+				.build();
+
+			new_evidence_validator_records.push(evidence_validator_record);
+		}
+	}
+
+	Ok(())
+}
+
+// events and their attributes are inserted in chunks, since a batch of blocks may easily
+// contain enough of them to exceed the bind parameter limit of a single postgres statement
+async fn insert_events(
+	conn: &mut AsyncPgConnection,
+	new_event_records: &[NewEventRecord<'_>],
+	new_event_attribute_records: &[NewEventAttributeRecord<'_>],
+) -> Result<(), diesel::result::Error> {
+	const EVENT_RECORDS_PER_INSERT: usize = MAX_BIND_PARAMS / 4;
+	const EVENT_ATTRIBUTE_RECORDS_PER_INSERT: usize = MAX_BIND_PARAMS / 6;
+
+	for records in new_event_records.chunks(EVENT_RECORDS_PER_INSERT) {
+		diesel::insert_into(schema::event::table).values(records).execute(conn).await?;
+	}
+
+	for records in new_event_attribute_records.chunks(EVENT_ATTRIBUTE_RECORDS_PER_INSERT) {
+		diesel::insert_into(schema::event_attribute::table).values(records).execute(conn).await?;
 	}
 
 	Ok(())
 }
 
+#[tracing::instrument(skip(conn))]
+pub async fn fetch_txs_by_address(
+	conn: &mut DbConn,
+	address: &Address,
+	before: Option<TxCursor>,
+	limit: NonZeroU64,
+) -> Result<Page<Tx, TxCursor>, StoreError> {
+	let mut query = schema::account_tx::table
+		.inner_join(
+			schema::tx::table.on(schema::tx::block_height
+				.eq(schema::account_tx::block_height)
+				.and(schema::tx::tx_idx_in_block.eq(schema::account_tx::tx_idx_in_block))),
+		)
+		.select(schema::tx::all_columns)
+		.filter(schema::account_tx::address.eq(address.as_bytes()))
+		.order((
+			schema::account_tx::block_height.desc(),
+			schema::account_tx::tx_idx_in_block.desc(),
+		))
+		// fetching one more than the limit tells whether there is a next page
+		.limit(i64::try_from(limit.get()).map_err(InvalidValueError::from)?.saturating_add(1))
+		.into_boxed();
+
+	if let Some(before) = before {
+		let height = i64::try_from(before.block_height().get()).map_err(InvalidValueError::from)?;
+		let tx_idx_in_block =
+			i64::try_from(before.tx_idx_in_block()).map_err(InvalidValueError::from)?;
+
+		query = query.filter(
+			schema::account_tx::block_height.lt(height).or(schema::account_tx::block_height
+				.eq(height)
+				.and(schema::account_tx::tx_idx_in_block.lt(tx_idx_in_block))),
+		);
+	}
+
+	let mut tx_records = query.load::<TxRecord>(conn).await?;
+	let has_next = tx_records.len() as u64 > limit.get();
+	tx_records.truncate(usize::try_from(limit.get()).unwrap_or(usize::MAX));
+
+	let txs = fetch_txs_details(conn, tx_records).await?;
+	let next_cursor = has_next.then(|| txs.last().map(TxCursor::from)).flatten();
+
+	Ok(Page::builder().items(txs).maybe_next_cursor(next_cursor).build())
+}
+
+pub async fn fetch_blocks_by_heights(
+	conn: &mut DbConn,
+	heights: &[NonZeroU64],
+) -> Result<Vec<Block>, StoreError> {
+	let heights = to_db_heights(heights)?;
+	let tx_bz_array_agg = dsl::sql::<Array<Bytea>>(
+		"COALESCE(NULLIF(array_agg(tx.tx_bz ORDER BY tx.tx_idx_in_block ASC), '{NULL}'), '{}')",
+	);
+
+	schema::block::table
+		.left_join(schema::tx::table.on(schema::tx::block_height.eq(schema::block::height)))
+		.filter(schema::block::height.eq_any(heights))
+		.select((schema::block::all_columns, tx_bz_array_agg))
+		.group_by(schema::block::all_columns)
+		.order(schema::block::height.asc())
+		.load::<BlockWithDataRecord>(conn)
+		.await?
+		.into_iter()
+		.map(TryFrom::try_from)
+		.collect::<Result<_, _>>()
+		.map_err(From::from)
+}
+
+// txs of the block in their order in the block, fetched with a constant number of queries
+pub async fn fetch_txs_by_block_height(
+	conn: &mut DbConn,
+	height: NonZeroU64,
+) -> Result<Vec<Tx>, StoreError> {
+	fetch_txs_by_block_heights(conn, &[height]).await
+}
+
+// txs of the blocks in their order in the chain, fetched with a constant number of queries
+// regardless of the number of blocks and txs
+pub async fn fetch_txs_by_block_heights(
+	conn: &mut DbConn,
+	heights: &[NonZeroU64],
+) -> Result<Vec<Tx>, StoreError> {
+	let heights = to_db_heights(heights)?;
+
+	let txs = schema::tx::table
+		.select(schema::tx::all_columns)
+		.filter(schema::tx::block_height.eq_any(&heights))
+		.order((
+			schema::tx::block_height.asc(),
+			schema::tx::tx_idx_in_block.asc(),
+		))
+		.load::<TxRecord>(conn)
+		.await?;
+
+	fetch_txs_details(conn, txs).await
+}
+
+pub async fn fetch_blocks(
+	conn: &mut DbConn,
+	range: HeightRange,
+	after: Option<NonZeroU64>,
+	order: Order,
+	limit: NonZeroU64,
+) -> Result<Page<Block, NonZeroU64>, StoreError> {
+	let mut query = schema::block::table
+		.select(schema::block::height)
+		// fetching one more than the limit tells whether there is a next page
+		.limit(i64::try_from(limit.get()).map_err(InvalidValueError::from)?.saturating_add(1))
+		.into_boxed();
+
+	if let Some(lo) = range.lo() {
+		query = query.filter(schema::block::height.ge(to_db_height(lo)?));
+	}
+
+	if let Some(hi) = range.hi() {
+		query = query.filter(schema::block::height.le(to_db_height(hi)?));
+	}
+
+	query = match order {
+		Order::Asc => query.order(schema::block::height.asc()),
+		Order::Desc => query.order(schema::block::height.desc()),
+	};
+
+	if let Some(after) = after {
+		let after = to_db_height(after)?;
+		query = match order {
+			Order::Asc => query.filter(schema::block::height.gt(after)),
+			Order::Desc => query.filter(schema::block::height.lt(after)),
+		};
+	}
+
+	let mut heights = query
+		.load::<i64>(conn)
+		.await?
+		.into_iter()
+		.map(|h| u64::try_from(h).and_then(NonZeroU64::try_from))
+		.collect::<Result<Vec<_>, _>>()
+		.map_err(InvalidValueError::from)?;
+
+	let has_next = heights.len() as u64 > limit.get();
+	heights.truncate(usize::try_from(limit.get()).unwrap_or(usize::MAX));
+
+	let mut blocks = fetch_blocks_by_heights(conn, &heights).await?;
+	if order == Order::Desc {
+		blocks.reverse();
+	}
+
+	let next_cursor = has_next.then(|| heights.last().copied()).flatten();
+
+	Ok(Page::builder().items(blocks).maybe_next_cursor(next_cursor).build())
+}
+
+// txs of the blocks in the range, continuing after the cursor in the given order
+pub async fn fetch_txs(
+	conn: &mut DbConn,
+	range: HeightRange,
+	after: Option<TxCursor>,
+	order: Order,
+	limit: NonZeroU64,
+) -> Result<Page<Tx, TxCursor>, StoreError> {
+	search_txs(conn, &range.into(), after, order, limit).await
+}
+
+// txs matching the filter, continuing after the cursor in the given order
+pub async fn search_txs(
+	conn: &mut DbConn,
+	filter: &TxFilter,
+	after: Option<TxCursor>,
+	order: Order,
+	limit: NonZeroU64,
+) -> Result<Page<Tx, TxCursor>, StoreError> {
+	let mut query = schema::tx::table
+		.select(schema::tx::all_columns)
+		// fetching one more than the limit tells whether there is a next page
+		.limit(i64::try_from(limit.get()).map_err(InvalidValueError::from)?.saturating_add(1))
+		.into_boxed();
+
+	let range = filter.heights();
+
+	if let Some(lo) = range.lo() {
+		query = query.filter(schema::tx::block_height.ge(to_db_height(lo)?));
+	}
+
+	if let Some(hi) = range.hi() {
+		query = query.filter(schema::tx::block_height.le(to_db_height(hi)?));
+	}
+
+	// block times are monotonic, hence the time range maps to a height range
+	if let Some(from) = filter.time_from() {
+		let from = jiff_to_chrono(&from).ok_or(InvalidValueError::Time)?;
+		query = query.filter(
+			schema::tx::block_height.nullable().ge(schema::block::table
+				.select(dsl::min(schema::block::height))
+				.filter(schema::block::time.ge(from))
+				.single_value()),
+		);
+	}
+
+	if let Some(to) = filter.time_to() {
+		let to = jiff_to_chrono(&to).ok_or(InvalidValueError::Time)?;
+		query = query.filter(
+			schema::tx::block_height.nullable().le(schema::block::table
+				.select(dsl::max(schema::block::height))
+				.filter(schema::block::time.le(to))
+				.single_value()),
+		);
+	}
+
+	if let Some(type_url) = filter.type_url() {
+		query = query.filter(dsl::exists(
+			schema::msg::table
+				.filter(schema::msg::block_height.eq(schema::tx::block_height))
+				.filter(schema::msg::tx_idx_in_block.eq(schema::tx::tx_idx_in_block))
+				.filter(schema::msg::type_url.eq(type_url.to_owned())),
+		));
+	}
+
+	if let Some(code) = filter.code() {
+		query = query
+			.filter(schema::tx::code.eq(i32::try_from(code).map_err(InvalidValueError::from)?));
+	}
+
+	query = match filter.failed() {
+		Some(true) => query.filter(schema::tx::code.ne(0)),
+		Some(false) => query.filter(schema::tx::code.eq(0)),
+		None => query,
+	};
+
+	if let Some(codespace) = filter.codespace() {
+		query = query.filter(schema::tx::codespace.eq(codespace.to_owned()));
+	}
+
+	if let Some(memo) = filter.memo_contains() {
+		query = query.filter(schema::tx::memo.like(format!("%{}%", escape_like(memo))));
+	}
+
+	query = match order {
+		Order::Asc => query.order((
+			schema::tx::block_height.asc(),
+			schema::tx::tx_idx_in_block.asc(),
+		)),
+		Order::Desc => query.order((
+			schema::tx::block_height.desc(),
+			schema::tx::tx_idx_in_block.desc(),
+		)),
+	};
+
+	if let Some(after) = after {
+		let height = to_db_height(after.block_height())?;
+		let tx_idx_in_block =
+			i64::try_from(after.tx_idx_in_block()).map_err(InvalidValueError::from)?;
+
+		query = match order {
+			Order::Asc => query.filter(
+				schema::tx::block_height.gt(height).or(schema::tx::block_height
+					.eq(height)
+					.and(schema::tx::tx_idx_in_block.gt(tx_idx_in_block))),
+			),
+			Order::Desc => query.filter(
+				schema::tx::block_height.lt(height).or(schema::tx::block_height
+					.eq(height)
+					.and(schema::tx::tx_idx_in_block.lt(tx_idx_in_block))),
+			),
+		};
+	}
+
+	let mut tx_records = query.load::<TxRecord>(conn).await?;
+	let has_next = tx_records.len() as u64 > limit.get();
+	tx_records.truncate(usize::try_from(limit.get()).unwrap_or(usize::MAX));
+
+	let txs = fetch_txs_details(conn, tx_records).await?;
+	let next_cursor = has_next.then(|| txs.last().map(TxCursor::from)).flatten();
+
+	Ok(Page::builder().items(txs).maybe_next_cursor(next_cursor).build())
+}
+
+// evidence committed in the blocks in the range, optionally only the ones the validator offended
+// in, continuing after the cursor in the given order
+pub async fn fetch_evidence(
+	conn: &mut DbConn,
+	range: HeightRange,
+	validator: Option<&Address>,
+	after: Option<EvidenceCursor>,
+	order: Order,
+	limit: NonZeroU64,
+) -> Result<Page<Evidence, EvidenceCursor>, StoreError> {
+	let mut query = schema::evidence::table
+		.select(schema::evidence::all_columns)
+		// fetching one more than the limit tells whether there is a next page
+		.limit(i64::try_from(limit.get()).map_err(InvalidValueError::from)?.saturating_add(1))
+		.into_boxed();
+
+	if let Some(lo) = range.lo() {
+		query = query.filter(schema::evidence::block_height.ge(to_db_height(lo)?));
+	}
+
+	if let Some(hi) = range.hi() {
+		query = query.filter(schema::evidence::block_height.le(to_db_height(hi)?));
+	}
+
+	if let Some(validator) = validator {
+		query = query.filter(dsl::exists(
+			schema::evidence_validator::table
+				.filter(schema::evidence_validator::block_height.eq(schema::evidence::block_height))
+				.filter(
+					schema::evidence_validator::evidence_idx_in_block
+						.eq(schema::evidence::evidence_idx_in_block),
+				)
+				.filter(schema::evidence_validator::address.eq(validator.as_bytes())),
+		));
+	}
+
+	query = match order {
+		Order::Asc => query.order((
+			schema::evidence::block_height.asc(),
+			schema::evidence::evidence_idx_in_block.asc(),
+		)),
+		Order::Desc => query.order((
+			schema::evidence::block_height.desc(),
+			schema::evidence::evidence_idx_in_block.desc(),
+		)),
+	};
+
+	if let Some(after) = after {
+		let height = to_db_height(after.block_height())?;
+		let evidence_idx_in_block =
+			i64::try_from(after.evidence_idx_in_block()).map_err(InvalidValueError::from)?;
+
+		query = match order {
+			Order::Asc => query.filter(
+				schema::evidence::block_height.gt(height).or(schema::evidence::block_height
+					.eq(height)
+					.and(schema::evidence::evidence_idx_in_block.gt(evidence_idx_in_block))),
+			),
+			Order::Desc => query.filter(
+				schema::evidence::block_height.lt(height).or(schema::evidence::block_height
+					.eq(height)
+					.and(schema::evidence::evidence_idx_in_block.lt(evidence_idx_in_block))),
+			),
+		};
+	}
+
+	let mut evidence_records = query.load::<EvidenceRecord>(conn).await?;
+	let has_next = evidence_records.len() as u64 > limit.get();
+	evidence_records.truncate(usize::try_from(limit.get()).unwrap_or(usize::MAX));
+
+	let mut heights = evidence_records.iter().map(EvidenceRecord::block_height).collect::<Vec<_>>();
+	heights.sort_unstable();
+	heights.dedup();
+
+	let mut validators = group_by_key(
+		schema::evidence_validator::table
+			.select((
+				schema::evidence_validator::block_height,
+				schema::evidence_validator::evidence_idx_in_block,
+				schema::evidence_validator::address,
+			))
+			.filter(schema::evidence_validator::block_height.eq_any(&heights))
+			.order(schema::evidence_validator::validator_idx_in_evidence.asc())
+			.load::<(i64, i64, Vec<u8>)>(conn)
+			.await?,
+	);
+
+	let evidence = evidence_records
+		.into_iter()
+		.map(|evidence| {
+			let key = (evidence.block_height(), evidence.evidence_idx_in_block());
+			EvidenceWithValidatorsRecord::builder()
+				.validators(validators.remove(&key).unwrap_or_default())
+				.evidence(evidence)
+				.build()
+				.try_into()
+		})
+		.collect::<Result<Vec<Evidence>, _>>()?;
+
+	let next_cursor = has_next.then(|| evidence.last().map(EvidenceCursor::from)).flatten();
+
+	Ok(Page::builder().items(evidence).maybe_next_cursor(next_cursor).build())
+}
+
+// fetches the details of the txs with a constant number of queries regardless of the number of
+// txs, by loading the details of all the txs in the blocks of the given txs
+async fn fetch_txs_details(conn: &mut DbConn, txs: Vec<TxRecord>) -> Result<Vec<Tx>, StoreError> {
+	if txs.is_empty() {
+		return Ok(vec![]);
+	}
+
+	let (tx_heights, tx_idxs) = txs
+		.iter()
+		.map(|tx| (tx.block_height(), tx.tx_idx_in_block()))
+		.unzip::<_, _, Vec<_>, Vec<_>>();
+
+	let mut heights = tx_heights.clone();
+	heights.sort_unstable();
+	heights.dedup();
+
+	// only the rows of the given txs, rather than of every tx in their blocks; diesel lacks row
+	// value comparisons, whereas the filter on the heights alone lets the planner use the indexes
+	let of_txs = |table: &str| {
+		dsl::sql::<Bool>(&format!(
+			"({table}.block_height, {table}.tx_idx_in_block) IN (SELECT * FROM unnest("
+		))
+		.bind::<Array<BigInt>, _>(&tx_heights)
+		.sql(", ")
+		.bind::<Array<BigInt>, _>(&tx_idxs)
+		.sql("))")
+	};
+
+	let mut signatures = group_by_key(
+		schema::signature::table
+			.select((
+				schema::signature::block_height,
+				schema::signature::tx_idx_in_block,
+				(schema::signature::bz,),
+			))
+			.filter(schema::signature::block_height.eq_any(&heights))
+			.filter(of_txs("signature"))
+			.order(schema::signature::signature_idx_in_tx.asc())
+			.load::<(i64, i64, SignatureRecord)>(conn)
+			.await?,
+	);
+
+	let mut fees = group_by_key(
+		schema::fee::table
+			.select((
+				schema::fee::block_height,
+				schema::fee::tx_idx_in_block,
+				(schema::fee::amount, schema::fee::denom),
+			))
+			.filter(schema::fee::block_height.eq_any(&heights))
+			.filter(of_txs("fee"))
+			.order(schema::fee::fee_idx_in_tx.asc())
+			.load::<(i64, i64, FeeRecord)>(conn)
+			.await?,
+	);
+
+	let mut msgs = group_by_key(
+		schema::msg::table
+			.select((
+				schema::msg::block_height,
+				schema::msg::tx_idx_in_block,
+				(schema::msg::type_url, schema::msg::value, schema::msg::json),
+			))
+			.filter(schema::msg::block_height.eq_any(&heights))
+			.filter(of_txs("msg"))
+			.order(schema::msg::msg_idx_in_tx.asc())
+			.load::<(i64, i64, MsgRecord)>(conn)
+			.await?,
+	);
+
+	let mut accounts = group_by_key(
+		schema::account_tx::table
+			.select((
+				schema::account_tx::block_height,
+				schema::account_tx::tx_idx_in_block,
+				(schema::account_tx::address,),
+			))
+			.filter(schema::account_tx::block_height.eq_any(&heights))
+			.filter(of_txs("account_tx"))
+			.order(schema::account_tx::address.asc())
+			.load::<(i64, i64, AccountTxRecord)>(conn)
+			.await?,
+	);
+
+	txs.into_iter()
+		.map(|tx| {
+			let key = (tx.block_height(), tx.tx_idx_in_block());
+
+			TxWithDetailsRecord::builder()
+				.signatures(
+					signatures
+						.remove(&key)
+						.unwrap_or_default()
+						.into_iter()
+						.map(SignatureRecord::into_bytes)
+						.collect(),
+				)
+				.fees(
+					fees.remove(&key)
+						.unwrap_or_default()
+						.iter()
+						.map(TryFrom::try_from)
+						.collect::<Result<_, _>>()?,
+				)
+				.msgs(msgs.remove(&key).unwrap_or_default())
+				.accounts(
+					accounts
+						.remove(&key)
+						.unwrap_or_default()
+						.into_iter()
+						.map(AccountTxRecord::into_bytes)
+						.collect(),
+				)
+				.tx(tx)
+				.build()
+				.try_into()
+		})
+		.collect::<Result<_, InvalidValueError>>()
+		.map_err(From::from)
+}
+
+async fn fetch_tx_details(conn: &mut DbConn, tx: TxRecord) -> Result<Tx, StoreError> {
+	let height = tx.block_height();
+	let tx_idx_in_block = tx.tx_idx_in_block();
+
+	let signatures = fetch_signatures(conn, height, tx_idx_in_block).await?;
+	let fee = fetch_fee(conn, height, tx_idx_in_block).await?;
+	let msgs = fetch_msgs(conn, height, tx_idx_in_block).await?;
+	let accounts = fetch_accounts(conn, height, tx_idx_in_block).await?;
+
+	TxWithDetailsRecord::builder()
+		.tx(tx)
+		.signatures(signatures.into_iter().map(SignatureRecord::into_bytes).collect())
+		.msgs(msgs)
+		.fees(fee.iter().map(TryFrom::try_from).collect::<Result<_, _>>()?)
+		.accounts(accounts.into_iter().map(AccountTxRecord::into_bytes).collect())
+		.build()
+		.try_into()
+		.map_err(From::from)
+}
+
+async fn fetch_validators(
+	conn: &mut DbConn,
+	validators_hash: &[u8],
+) -> Result<Vec<Validator>, StoreError> {
+	schema::validator_set::table
+		.select((
+			schema::validator_set::address,
+			schema::validator_set::pub_key_type,
+			schema::validator_set::pub_key,
+			schema::validator_set::voting_power,
+		))
+		.filter(schema::validator_set::validators_hash.eq(validators_hash))
+		.order(schema::validator_set::validator_idx.asc())
+		.load::<ValidatorRecord>(conn)
+		.await?
+		.into_iter()
+		.map(Validator::try_from)
+		.collect::<Result<_, _>>()
+		.map_err(From::from)
+}
+
 async fn fetch_signatures(
 	conn: &mut DbConn,
 	height: i64,
@@ -338,10 +1668,59 @@ async fn fetch_msgs(
 	tx_idx_in_block: i64,
 ) -> Result<Vec<MsgRecord>, diesel::result::Error> {
 	schema::msg::table
-		.select((schema::msg::type_url, schema::msg::value))
+		.select((schema::msg::type_url, schema::msg::value, schema::msg::json))
 		.filter(schema::msg::block_height.eq(height))
 		.filter(schema::msg::tx_idx_in_block.eq(tx_idx_in_block))
 		.order(schema::msg::msg_idx_in_tx.asc())
 		.load(conn)
 		.await
 }
+
+async fn fetch_accounts(
+	conn: &mut DbConn,
+	height: i64,
+	tx_idx_in_block: i64,
+) -> Result<Vec<AccountTxRecord>, diesel::result::Error> {
+	schema::account_tx::table
+		.select((schema::account_tx::address,))
+		.filter(schema::account_tx::block_height.eq(height))
+		.filter(schema::account_tx::tx_idx_in_block.eq(tx_idx_in_block))
+		.order(schema::account_tx::address.asc())
+		.load(conn)
+		.await
+}
+
+fn to_db_height(height: NonZeroU64) -> Result<i64, InvalidValueError> {
+	i64::try_from(height.get()).map_err(From::from)
+}
+
+fn to_db_heights(heights: &[NonZeroU64]) -> Result<Vec<i64>, InvalidValueError> {
+	heights.iter().copied().map(to_db_height).collect()
+}
+
+// escapes the LIKE wildcards, so that the pattern matches literally
+fn escape_like(pattern: &str) -> String {
+	pattern.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_")
+}
+
+// groups the rows by the pair of keys leading them, such as the position of the tx or the
+// evidence they belong to, keeping the order of the rows within each group
+fn group_by_key<T>(rows: Vec<(i64, i64, T)>) -> HashMap<(i64, i64), Vec<T>> {
+	rows.into_iter().fold(HashMap::new(), |mut groups, (height, idx_in_block, row)| {
+		groups.entry((height, idx_in_block)).or_default().push(row);
+		groups
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::escape_like;
+
+	#[test]
+	fn escape_like_wildcards() {
+		assert_eq!(escape_like("memo"), "memo");
+		assert_eq!(escape_like("100%"), "100\\%");
+		assert_eq!(escape_like("a_b"), "a\\_b");
+		assert_eq!(escape_like("a\\%b"), "a\\\\\\%b");
+	}
+}
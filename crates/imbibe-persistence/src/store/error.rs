@@ -1,3 +1,5 @@
+use imbibe_domain::Sha256;
+
 use crate::record::error::InvalidValueError;
 
 #[derive(Debug, thiserror::Error)]
@@ -7,4 +9,29 @@ pub enum StoreError {
 
 	#[error("invalid value error: {0}")]
 	InvalidValue(#[from] InvalidValueError),
+
+	#[error(
+		"block hash mismatch error: block {height} is stored with hash {stored:?}, got {new:?}"
+	)]
+	BlockHashMismatch {
+		height: u64,
+		stored: Sha256,
+		new: Sha256,
+	},
+
+	#[cfg(feature = "database")]
+	#[error("listen error: {0}")]
+	Listen(#[from] tokio_postgres::Error),
+
+	#[error("listen error: connection closed")]
+	ListenClosed,
+
+	#[error("listen error: the db url requires TLS with sslmode=require, which is not supported")]
+	ListenTlsRequired,
+}
+
+impl StoreError {
+	pub fn is_not_found(&self) -> bool {
+		matches!(self, Self::Db(diesel::result::Error::NotFound))
+	}
 }
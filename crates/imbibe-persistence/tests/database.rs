@@ -0,0 +1,676 @@
+// runs against the database at `IMBIBE_TEST_DATABASE_URL`, migrating it if needed, and skips the
+// tests when it is not set; each test runs in a transaction which is never committed
+use core::num::{NonZeroU32, NonZeroU64, NonZeroUsize};
+
+use bytes::Bytes;
+use cosmrs::{
+	Any, Coin,
+	tendermint::{PublicKey, abci::Code},
+};
+use diesel::{
+	QueryableByName,
+	sql_types::{BigInt, Integer, Text},
+};
+use diesel_async::{AsyncConnection, RunQueryDsl};
+use futures::TryStreamExt;
+use imbibe_domain::{
+	Address, NonEmptyBz, Sha256,
+	block::{AppHash, Block, BlockData, Header},
+	filter::TxFilter,
+	page::{HeightRange, Order, TxCursor},
+	tx::{Codespace, Fees, Memo, Msgs, Tx},
+	validator::{Validator, ValidatorSet},
+};
+use imbibe_persistence::{
+	migration,
+	pool::{self, DbConn},
+	store::{self, StoreError, WriteMode},
+};
+use jiff::Timestamp;
+use tokio::sync::OnceCell;
+
+const CHAIN_ID: &str = "imbibe-test";
+
+static MIGRATED: OnceCell<()> = OnceCell::const_new();
+
+async fn conn() -> Option<DbConn> {
+	let Ok(url) = std::env::var("IMBIBE_TEST_DATABASE_URL") else {
+		eprintln!("IMBIBE_TEST_DATABASE_URL is not set, skipping");
+		return None;
+	};
+
+	MIGRATED
+		.get_or_init(|| {
+			let url = url.clone();
+			async move {
+				tokio::task::spawn_blocking(move || migration::run_pending_migrations(&url))
+					.await
+					.unwrap()
+					.unwrap();
+			}
+		})
+		.await;
+
+	let pool = pool::establish_pool(url, NonZeroUsize::MIN).await.unwrap();
+	let mut conn = pool.get().await.unwrap();
+	conn.begin_test_transaction().await.unwrap();
+
+	Some(conn)
+}
+
+fn height(height: u64) -> NonZeroU64 {
+	NonZeroU64::new(height).unwrap()
+}
+
+// the block hash is derived from the chain id and the height, unless given explicitly
+fn block(chain_id: &str, height: u64) -> Block {
+	let mut hash = [0; Sha256::LEN];
+	hash[..8].copy_from_slice(&height.to_be_bytes());
+	hash[8] = chain_id.len() as u8;
+
+	block_with_hash(chain_id, height, Sha256::new(hash))
+}
+
+fn block_with_hash(chain_id: &str, height: u64, hash: Sha256) -> Block {
+	let header = Header::builder()
+		.chain_id(chain_id.into())
+		.height(height)
+		.time(Timestamp::from_second(height as i64).unwrap())
+		.validators_hash(Sha256::new([1; Sha256::LEN]))
+		.next_validators_hash(Sha256::new([1; Sha256::LEN]))
+		.consensus_hash(Sha256::new([2; Sha256::LEN]))
+		.app_hash(AppHash::new(vec![3; 32]))
+		.proposer(Address::new([4; 20]))
+		.build();
+
+	Block::builder()
+		.header(header)
+		.gas_used(0)
+		.hash(hash)
+		.data(BlockData::new(vec![]).unwrap())
+		.build()
+}
+
+async fn save(conn: &mut DbConn, block: &Block) {
+	store::save_block_with_txs(conn, block, &[], &[], &[], &[], WriteMode::Strict).await.unwrap();
+}
+
+#[tokio::test]
+async fn advance_indexed_height() {
+	let Some(mut conn) = conn().await else {
+		return;
+	};
+	let conn = &mut conn;
+
+	store::raise_start_height(conn, CHAIN_ID, height(1)).await.unwrap();
+	assert_eq!(
+		store::fetch_indexed_height(conn, CHAIN_ID).await.unwrap(),
+		None
+	);
+
+	// contiguous
+	for h in 1..=3 {
+		save(conn, &block(CHAIN_ID, h)).await;
+		assert_eq!(
+			store::advance_indexed_height(conn, CHAIN_ID).await.unwrap(),
+			Some(height(h))
+		);
+	}
+
+	// gapped, the watermark stays below the gap until it is filled
+	save(conn, &block(CHAIN_ID, 5)).await;
+	assert_eq!(
+		store::advance_indexed_height(conn, CHAIN_ID).await.unwrap(),
+		None
+	);
+	assert_eq!(
+		store::fetch_indexed_height(conn, CHAIN_ID).await.unwrap(),
+		Some(height(3))
+	);
+
+	save(conn, &block(CHAIN_ID, 4)).await;
+	assert_eq!(
+		store::advance_indexed_height(conn, CHAIN_ID).await.unwrap(),
+		Some(height(5))
+	);
+
+	// out of order, the watermark jumps over the whole run once it connects
+	for h in [8, 7] {
+		save(conn, &block(CHAIN_ID, h)).await;
+		assert_eq!(
+			store::advance_indexed_height(conn, CHAIN_ID).await.unwrap(),
+			None
+		);
+	}
+
+	save(conn, &block(CHAIN_ID, 6)).await;
+	assert_eq!(
+		store::advance_indexed_height(conn, CHAIN_ID).await.unwrap(),
+		Some(height(8))
+	);
+
+	// the blocks of another chain do not move the watermark
+	save(conn, &block("imbibe-other", 9)).await;
+	assert_eq!(
+		store::advance_indexed_height(conn, CHAIN_ID).await.unwrap(),
+		None
+	);
+	assert_eq!(
+		store::fetch_indexed_height(conn, CHAIN_ID).await.unwrap(),
+		Some(height(8))
+	);
+}
+
+#[tokio::test]
+async fn raise_start_height() {
+	let Some(mut conn) = conn().await else {
+		return;
+	};
+	let conn = &mut conn;
+
+	assert_eq!(
+		store::raise_start_height(conn, CHAIN_ID, height(5)).await.unwrap(),
+		4
+	);
+	assert_eq!(
+		store::fetch_start_height(conn, CHAIN_ID).await.unwrap(),
+		Some(height(5))
+	);
+
+	// lowering the start is ignored
+	assert_eq!(
+		store::raise_start_height(conn, CHAIN_ID, height(3)).await.unwrap(),
+		4
+	);
+	assert_eq!(
+		store::fetch_start_height(conn, CHAIN_ID).await.unwrap(),
+		Some(height(5))
+	);
+
+	for h in 5..=6 {
+		save(conn, &block(CHAIN_ID, h)).await;
+	}
+	store::advance_indexed_height(conn, CHAIN_ID).await.unwrap();
+
+	// raising the start within the indexed blocks keeps the watermark
+	assert_eq!(
+		store::raise_start_height(conn, CHAIN_ID, height(6)).await.unwrap(),
+		6
+	);
+	assert_eq!(
+		store::fetch_start_height(conn, CHAIN_ID).await.unwrap(),
+		Some(height(5))
+	);
+
+	// raising the start over unindexed blocks moves the watermark below it
+	assert_eq!(
+		store::raise_start_height(conn, CHAIN_ID, height(10)).await.unwrap(),
+		9
+	);
+	assert_eq!(
+		store::fetch_start_height(conn, CHAIN_ID).await.unwrap(),
+		Some(height(10))
+	);
+	assert_eq!(
+		store::fetch_indexed_height(conn, CHAIN_ID).await.unwrap(),
+		Some(height(9))
+	);
+}
+
+#[tokio::test]
+async fn save_blocks_idempotently() {
+	let Some(mut conn) = conn().await else {
+		return;
+	};
+	let conn = &mut conn;
+
+	let stored = block(CHAIN_ID, 20);
+	save(conn, &stored).await;
+
+	let save_with = async |conn: &mut DbConn, block: &Block, mode| {
+		store::save_blocks_with_txs(conn, &[(block.clone(), [], [], [], [])], mode).await
+	};
+
+	// strict writes fail on any stored block
+	assert!(matches!(
+		save_with(conn, &stored, WriteMode::Strict).await,
+		Err(StoreError::Db(_))
+	));
+
+	// idempotent writes skip the blocks stored with the same hash and save the others
+	save_with(conn, &stored, WriteMode::Idempotent).await.unwrap();
+	save_with(conn, &block(CHAIN_ID, 21), WriteMode::Idempotent).await.unwrap();
+	assert_eq!(
+		store::fetch_block_by_height(conn, height(21)).await.unwrap().hash().get(),
+		block(CHAIN_ID, 21).hash().get()
+	);
+
+	// and fail on the blocks stored with another hash
+	let conflicting = block_with_hash(CHAIN_ID, 20, Sha256::new([9; Sha256::LEN]));
+	match save_with(conn, &conflicting, WriteMode::Idempotent).await {
+		Err(StoreError::BlockHashMismatch { height, stored: s, new }) => {
+			assert_eq!(height, 20);
+			assert_eq!(s.get(), stored.hash().get());
+			assert_eq!(new.get(), conflicting.hash().get());
+		},
+		res => panic!("expected a block hash mismatch, got {res:?}"),
+	}
+}
+
+#[tokio::test]
+async fn dead_letter_failed_blocks() {
+	#[derive(QueryableByName)]
+	struct FailedBlock {
+		#[diesel(sql_type = BigInt)]
+		height: i64,
+		#[diesel(sql_type = Integer)]
+		attempts: i32,
+		#[diesel(sql_type = Text)]
+		error: String,
+	}
+
+	let Some(mut conn) = conn().await else {
+		return;
+	};
+	let conn = &mut conn;
+
+	let fetch = async |conn: &mut DbConn| {
+		diesel::sql_query(
+			"SELECT height, attempts, error FROM failed_block WHERE chain_id = $1 ORDER BY height",
+		)
+		.bind::<Text, _>(CHAIN_ID)
+		.load::<FailedBlock>(conn)
+		.await
+		.unwrap()
+		.into_iter()
+		.map(|f| (f.height, f.attempts, f.error))
+		.collect::<Vec<_>>()
+	};
+
+	let attempts = |n| NonZeroU32::new(n).unwrap();
+	store::save_failed_block(conn, CHAIN_ID, 30, attempts(3), "first").await.unwrap();
+	store::save_failed_block(conn, CHAIN_ID, 31, attempts(3), "first").await.unwrap();
+
+	// the attempts of a block failing again accumulate, keeping the last error
+	store::save_failed_block(conn, CHAIN_ID, 30, attempts(2), "second").await.unwrap();
+	assert_eq!(
+		fetch(conn).await,
+		[(30, 5, "second".into()), (31, 3, "first".into())]
+	);
+
+	// the blocks indexed later on are removed
+	assert_eq!(
+		store::remove_failed_blocks(conn, CHAIN_ID, &[30, 32]).await.unwrap(),
+		1
+	);
+	assert_eq!(fetch(conn).await, [(31, 3, "first".into())]);
+}
+
+fn tx(height: u64, idx: u64) -> Tx {
+	tx_with(height, idx, Code::Ok, None, None)
+}
+
+fn tx_with(height: u64, idx: u64, code: Code, codespace: Option<&str>, memo: Option<&str>) -> Tx {
+	let n = idx as u8 + 1;
+	let mut tx_hash = [0; Sha256::LEN];
+	tx_hash[..8].copy_from_slice(&height.to_be_bytes());
+	tx_hash[8] = n;
+
+	Tx::builder()
+		.block_height(self::height(height))
+		.tx_idx_in_block(idx)
+		.tx_hash(Sha256::new(tx_hash))
+		.msgs(
+			Msgs::new(vec![Any {
+				type_url: format!("/imbibe.test.Msg{n}"),
+				value: vec![n],
+			}])
+			.unwrap(),
+		)
+		.signatures(vec![vec![n; 64]])
+		.signers(vec![])
+		.fees(
+			Fees::new(vec![Coin {
+				denom: "uimbibe".parse().unwrap(),
+				amount: n.into(),
+			}])
+			.unwrap(),
+		)
+		.payer(Address::new([n; 20]))
+		.accounts(vec![Address::new([n; 20])])
+		.code(code)
+		.maybe_codespace(codespace.and_then(|codespace| Codespace::new(codespace.into())))
+		.maybe_memo(memo.and_then(|memo| Memo::new(memo.into())))
+		.gas_limit(0)
+		.gas_wanted(0)
+		.gas_used(0)
+		.tx_bz(NonEmptyBz::new(Bytes::from(vec![n])).unwrap())
+		.build()
+}
+
+#[tokio::test]
+async fn fetch_txs_details() {
+	let Some(mut conn) = conn().await else {
+		return;
+	};
+	let conn = &mut conn;
+
+	let txs = (0..3).map(|idx| tx(40, idx)).collect::<Vec<_>>();
+	store::save_block_with_txs(
+		conn,
+		&block(CHAIN_ID, 40),
+		&txs,
+		&[],
+		&[],
+		&[],
+		WriteMode::Strict,
+	)
+	.await
+	.unwrap();
+
+	let details = |tx: &Tx| {
+		(
+			tx.tx_idx_in_block(),
+			tx.signatures().to_vec(),
+			tx.fees().map(|fees| fees.get()[0].amount),
+			tx.msgs().get()[0].type_url.clone(),
+			tx.accounts().iter().map(|a| a.as_bytes().to_vec()).collect::<Vec<_>>(),
+		)
+	};
+
+	// the details of a page of txs belong to its txs only, not to the other txs of their blocks
+	let page =
+		store::fetch_txs_by_address(conn, &Address::new([2; 20]), None, height(10)).await.unwrap();
+	assert_eq!(
+		page.items().iter().map(details).collect::<Vec<_>>(),
+		[details(&txs[1])]
+	);
+
+	let fetched = store::fetch_txs_by_block_height(conn, height(40)).await.unwrap();
+	assert_eq!(
+		fetched.iter().map(details).collect::<Vec<_>>(),
+		txs.iter().map(details).collect::<Vec<_>>()
+	);
+}
+
+#[tokio::test]
+async fn keyset_paging() {
+	let Some(mut conn) = conn().await else {
+		return;
+	};
+	let conn = &mut conn;
+
+	for h in 50..=54 {
+		let txs = match h {
+			50 => vec![tx(50, 0), tx(50, 1)],
+			52 => vec![tx(52, 0), tx(52, 1), tx(52, 2)],
+			_ => vec![],
+		};
+
+		store::save_block_with_txs(
+			conn,
+			&block(CHAIN_ID, h),
+			&txs,
+			&[],
+			&[],
+			&[],
+			WriteMode::Strict,
+		)
+		.await
+		.unwrap();
+	}
+
+	let range = HeightRange::builder().lo(height(50)).hi(height(54)).build();
+	let cursor = |h, idx| TxCursor::builder().block_height(height(h)).tx_idx_in_block(idx).build();
+
+	// blocks, ascending, walked to the last page
+	let mut after = None;
+	let mut pages = vec![];
+	loop {
+		let page = store::fetch_blocks(conn, range, after, Order::Asc, height(2)).await.unwrap();
+		pages.push(page.items().iter().map(|b| b.header().height()).collect::<Vec<_>>());
+
+		match page.next_cursor() {
+			Some(&next) => after = Some(next),
+			None => break,
+		}
+	}
+	assert_eq!(pages, [vec![50, 51], vec![52, 53], vec![54]]);
+
+	// blocks, descending within a narrower range
+	let range_51 = HeightRange::builder().lo(height(51)).hi(height(54)).build();
+	let page = store::fetch_blocks(conn, range_51, None, Order::Desc, height(3)).await.unwrap();
+	assert_eq!(
+		page.items().iter().map(|b| b.header().height()).collect::<Vec<_>>(),
+		[54, 53, 52]
+	);
+	assert_eq!(page.next_cursor(), Some(&height(52)));
+
+	let page = store::fetch_blocks(conn, range_51, Some(height(52)), Order::Desc, height(3))
+		.await
+		.unwrap();
+	assert_eq!(
+		page.items().iter().map(|b| b.header().height()).collect::<Vec<_>>(),
+		[51]
+	);
+	assert_eq!(page.next_cursor(), None);
+
+	let positions = |txs: &[Tx]| {
+		txs.iter().map(|tx| (tx.block_height().get(), tx.tx_idx_in_block())).collect::<Vec<_>>()
+	};
+
+	// txs, ascending, the cursor continues within a block and across blocks
+	let page = store::fetch_txs(conn, range, None, Order::Asc, height(3)).await.unwrap();
+	assert_eq!(positions(page.items()), [(50, 0), (50, 1), (52, 0)]);
+	assert_eq!(page.next_cursor(), Some(&cursor(52, 0)));
+
+	let page =
+		store::fetch_txs(conn, range, Some(cursor(52, 0)), Order::Asc, height(3)).await.unwrap();
+	assert_eq!(positions(page.items()), [(52, 1), (52, 2)]);
+	assert_eq!(page.next_cursor(), None);
+
+	// txs, descending
+	let page = store::fetch_txs(conn, range, None, Order::Desc, height(4)).await.unwrap();
+	assert_eq!(
+		positions(page.items()),
+		[(52, 2), (52, 1), (52, 0), (50, 1)]
+	);
+	assert_eq!(page.next_cursor(), Some(&cursor(50, 1)));
+
+	let page =
+		store::fetch_txs(conn, range, Some(cursor(50, 1)), Order::Desc, height(4)).await.unwrap();
+	assert_eq!(positions(page.items()), [(50, 0)]);
+	assert_eq!(page.next_cursor(), None);
+}
+
+#[tokio::test]
+async fn search_txs() {
+	let Some(mut conn) = conn().await else {
+		return;
+	};
+	let conn = &mut conn;
+
+	let failed = Code::Err(NonZeroU32::new(5).unwrap());
+	for (h, txs) in [
+		(
+			60,
+			vec![
+				tx_with(60, 0, Code::Ok, None, Some("100% sure")),
+				tx_with(60, 1, failed, Some("sdk"), Some("a_b")),
+			],
+		),
+		(
+			61,
+			vec![tx_with(61, 0, failed, Some("wasm"), Some("100 percent"))],
+		),
+		(62, vec![tx_with(62, 0, Code::Ok, None, Some("axb"))]),
+	] {
+		store::save_block_with_txs(
+			conn,
+			&block(CHAIN_ID, h),
+			&txs,
+			&[],
+			&[],
+			&[],
+			WriteMode::Strict,
+		)
+		.await
+		.unwrap();
+	}
+
+	let heights = HeightRange::builder().lo(height(60)).hi(height(62)).build();
+	let time = |h| Timestamp::from_second(h).unwrap();
+
+	let mut search = async |filter: TxFilter| {
+		store::search_txs(conn, &filter, None, Order::Asc, height(10))
+			.await
+			.unwrap()
+			.items()
+			.iter()
+			.map(|tx| (tx.block_height().get(), tx.tx_idx_in_block()))
+			.collect::<Vec<_>>()
+	};
+
+	assert_eq!(
+		search(TxFilter::builder().heights(heights).type_url("/imbibe.test.Msg2").build()).await,
+		[(60, 1)]
+	);
+	assert_eq!(
+		search(TxFilter::builder().heights(heights).code(5).build()).await,
+		[(60, 1), (61, 0)]
+	);
+	assert_eq!(
+		search(TxFilter::builder().heights(heights).failed(false).build()).await,
+		[(60, 0), (62, 0)]
+	);
+	assert_eq!(
+		search(TxFilter::builder().heights(heights).codespace("wasm").build()).await,
+		[(61, 0)]
+	);
+
+	// the wildcards of the memo match literally
+	assert_eq!(
+		search(TxFilter::builder().heights(heights).memo_contains("100%").build()).await,
+		[(60, 0)]
+	);
+	assert_eq!(
+		search(TxFilter::builder().heights(heights).memo_contains("a_b").build()).await,
+		[(60, 1)]
+	);
+
+	// the time range maps to the heights of the blocks within it
+	assert_eq!(
+		search(TxFilter::builder().time_from(time(61)).time_to(time(61)).build()).await,
+		[(61, 0)]
+	);
+	assert_eq!(
+		search(TxFilter::builder().time_from(time(61)).build()).await,
+		[(61, 0), (62, 0)]
+	);
+	assert_eq!(
+		search(TxFilter::builder().time_from(time(63)).build()).await,
+		[]
+	);
+
+	// the criteria that are set must all match
+	assert_eq!(
+		search(TxFilter::builder().heights(heights).code(5).codespace("sdk").build()).await,
+		[(60, 1)]
+	);
+}
+
+// a fork rolled back by the live indexer commits the removal before the blocks are reindexed,
+// hence a restart must find the removed blocks missing above the lowered watermark
+#[tokio::test]
+async fn roll_back_blocks() {
+	let Some(mut conn) = conn().await else {
+		return;
+	};
+	let conn = &mut conn;
+
+	store::raise_start_height(conn, CHAIN_ID, height(70)).await.unwrap();
+	for h in 70..=75 {
+		let txs = if h == 73 { vec![tx(73, 0)] } else { vec![] };
+		store::save_block_with_txs(
+			conn,
+			&block(CHAIN_ID, h),
+			&txs,
+			&[],
+			&[],
+			&[],
+			WriteMode::Strict,
+		)
+		.await
+		.unwrap();
+	}
+	store::advance_indexed_height(conn, CHAIN_ID).await.unwrap();
+
+	assert_eq!(
+		store::remove_blocks_from(conn, CHAIN_ID, height(72)).await.unwrap(),
+		4
+	);
+	assert_eq!(
+		store::fetch_indexed_height(conn, CHAIN_ID).await.unwrap(),
+		Some(height(71))
+	);
+	assert!(store::fetch_txs_by_block_height(conn, height(73)).await.unwrap().is_empty());
+
+	// on restart, the indexer resumes from the lowered watermark, and backfills the missing blocks
+	assert_eq!(
+		store::raise_start_height(conn, CHAIN_ID, height(70)).await.unwrap(),
+		71
+	);
+
+	let missing = store::fetch_missing_block_heights(conn, height(70), height(75))
+		.await
+		.unwrap()
+		.try_collect::<Vec<_>>()
+		.await
+		.unwrap();
+	assert_eq!(missing, (72..=75).map(height).collect::<Vec<_>>());
+
+	// the blocks of the fork are reindexed with the hashes of the node
+	let fork_hash = |h: u64| Sha256::new([h as u8; Sha256::LEN]);
+	for h in 72..=75 {
+		save(conn, &block_with_hash(CHAIN_ID, h, fork_hash(h))).await;
+	}
+
+	assert_eq!(
+		store::advance_indexed_height(conn, CHAIN_ID).await.unwrap(),
+		Some(height(75))
+	);
+	assert_eq!(
+		store::fetch_block_hash(conn, height(73)).await.unwrap().map(|hash| *hash.get()),
+		Some(*fork_hash(73).get())
+	);
+}
+
+#[tokio::test]
+async fn fetch_stored_validators_hashes() {
+	let Some(mut conn) = conn().await else {
+		return;
+	};
+	let conn = &mut conn;
+
+	let stored = Sha256::new([0x81; Sha256::LEN]);
+	let validator_set = ValidatorSet::builder()
+		.validators_hash(stored.clone())
+		.validators(vec![
+			Validator::builder()
+				.address(Address::new([0x81; 20]))
+				.pub_key(PublicKey::from_raw_ed25519(&[0x81; 32]).unwrap())
+				.voting_power(10)
+				.build(),
+		])
+		.build();
+	store::save_validator_set(conn, &validator_set).await.unwrap();
+
+	let hashes = [stored.clone(), Sha256::new([0x82; Sha256::LEN]), stored];
+	let fetched = store::fetch_stored_validators_hashes(conn, &hashes).await.unwrap();
+	assert_eq!(
+		fetched.iter().map(|hash| *hash.get()).collect::<Vec<_>>(),
+		[[0x81; Sha256::LEN]]
+	);
+
+	assert!(store::fetch_stored_validators_hashes(conn, &[]).await.unwrap().is_empty());
+}
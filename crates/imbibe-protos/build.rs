@@ -29,6 +29,9 @@ fn main() -> anyhow::Result<()> {
 	#[cfg(feature = "ethsecp256k1")]
 	prepare::buf_export_ethsecp256k1(&*global::PROTO_EXPORT_DIR)?;
 
+	#[cfg(feature = "grpc")]
+	prepare::copy_query_protos(&*global::QUERY_PROTO_DIR, &*global::PROTO_EXPORT_DIR)?;
+
 	#[cfg(feature = "protogen")]
 	{
 		prepare::exclude_protos()?;
@@ -1,3 +1,7 @@
+#[cfg(feature = "grpc")]
+#[path = "codegen/query.rs"]
+pub mod query;
+
 #[path = "codegen/signer.rs"]
 pub mod signer;
 
@@ -17,6 +21,9 @@ use crate::global::OUT_DIR;
 
 const GENERATED_DO_NOT_EDIT: &str = "// This is @generated by build.rs. DO NOT EDIT directly.\n";
 
+// embedded by the crate, must be kept in sync with `src/lib.rs`
+const FILE_DESCRIPTOR_SET_FILE_NAME: &str = "file_descriptor_set.bin";
+
 #[derive(Default, Debug)]
 pub struct ModuleNode {
 	submodules: BTreeMap<String, ModuleNode>,
@@ -44,6 +51,7 @@ where
 		// only generated to avoid compilation failure when no proto files exported
 		let mut file = File::create(OUT_DIR.join("any_signer_extractor.rs")).map(BufWriter::new)?;
 		writeln!(file, "{GENERATED_DO_NOT_EDIT}")?;
+		File::create(OUT_DIR.join(FILE_DESCRIPTOR_SET_FILE_NAME))?;
 
 		return Ok(());
 	};
@@ -60,6 +68,9 @@ where
 		config.type_attribute(msg_name, format!("#[signer_fields({})]", fields.join(",")));
 	});
 
+	#[cfg(feature = "grpc")]
+	config.service_generator(Box::new(query::QueryServiceGenerator::default()));
+
 	config
 		.out_dir(gen_dir.as_ref())
 		.enable_type_names()
@@ -189,7 +200,7 @@ where
 	P2: AsRef<Path>,
 	P3: AsRef<Path>,
 {
-	let fds_path = OUT_DIR.join("file_descriptor_set.bin");
+	let fds_path = OUT_DIR.join(FILE_DESCRIPTOR_SET_FILE_NAME);
 	Config::new()
 		.out_dir(gen_dir.as_ref())
 		.enable_type_names()
@@ -0,0 +1,30 @@
+use prost_build::{Service, ServiceGenerator};
+
+// package of the imbibe query service, shipped in `proto` with this crate
+const QUERY_PACKAGE: &str = "imbibe.query.v1";
+
+// generates the tonic client and server of the imbibe query service only, skipping the services of
+// the other compiled packages such as the cosmos msg and query services
+pub struct QueryServiceGenerator(Box<dyn ServiceGenerator>);
+
+impl Default for QueryServiceGenerator {
+	fn default() -> Self {
+		Self(tonic_build::configure().service_generator())
+	}
+}
+
+impl ServiceGenerator for QueryServiceGenerator {
+	fn generate(&mut self, service: Service, buf: &mut String) {
+		if service.package == QUERY_PACKAGE {
+			self.0.generate(service, buf);
+		}
+	}
+
+	fn finalize(&mut self, buf: &mut String) {
+		self.0.finalize(buf);
+	}
+
+	fn finalize_package(&mut self, package: &str, buf: &mut String) {
+		self.0.finalize_package(package, buf);
+	}
+}
@@ -17,6 +17,16 @@ pub static PROTO_SRC_DIR: LazyLock<PathBuf> = LazyLock::new(|| {
 		.into()
 });
 
+#[cfg(feature = "grpc")]
+pub static QUERY_PROTO_DIR: LazyLock<PathBuf> = LazyLock::new(|| {
+	const CARGO_MANIFEST_DIR_ENV_VAR: &str = "CARGO_MANIFEST_DIR";
+	env::var(CARGO_MANIFEST_DIR_ENV_VAR)
+		.inspect_err(|e| eprintln!("env var '{CARGO_MANIFEST_DIR_ENV_VAR}' must be set: {}", e))
+		.map(PathBuf::from)
+		.unwrap()
+		.join("proto")
+});
+
 pub static PROTO_EXPORT_DIR: LazyLock<PathBuf> = LazyLock::new(|| OUT_DIR.join("proto_export"));
 
 pub static CODE_GEN_DIR: LazyLock<PathBuf> = LazyLock::new(|| OUT_DIR.join("code_gen"));
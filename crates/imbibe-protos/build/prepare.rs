@@ -140,7 +140,38 @@ where
 	Ok(())
 }
 
-#[cfg(feature = "custom")]
+// copies the protos of the imbibe query service into the export directory, to be compiled along
+// with the exported ones
+#[cfg(feature = "grpc")]
+pub fn copy_query_protos<P1, P2>(query_proto_dir: P1, proto_export_dir: P2) -> anyhow::Result<()>
+where
+	P1: AsRef<Path>,
+	P2: AsRef<Path>,
+{
+	use walkdir::WalkDir;
+
+	let query_proto_dir = query_proto_dir.as_ref();
+	watch_dir_recursively(query_proto_dir);
+
+	for entry in WalkDir::new(query_proto_dir) {
+		let entry = entry?;
+		if !entry.file_type().is_file() {
+			continue;
+		}
+
+		let dest = proto_export_dir.as_ref().join(entry.path().strip_prefix(query_proto_dir)?);
+		if let Some(parent) = dest.parent() {
+			fs::create_dir_all(parent)?;
+		}
+
+		fs::copy(entry.path(), &dest)
+			.with_context(|| format!("failed copying {}", entry.path().display()))?;
+	}
+
+	Ok(())
+}
+
+#[cfg(any(feature = "custom", feature = "grpc"))]
 fn watch_dir_recursively<P>(path: P)
 where
 	P: AsRef<Path>,
@@ -7,4 +7,11 @@ pub enum ProtosError {
 
 	#[error("no signer in msg error: msg type url '{type_url}'")]
 	NoSignerInMsg { type_url: String },
+
+	#[error("unknown msg error: msg type url '{type_url}' not found in descriptor pool")]
+	UnknownMsg { type_url: String },
+
+	#[cfg(feature = "json")]
+	#[error("json error: {0}")]
+	Json(#[from] serde_json::Error),
 }
@@ -0,0 +1,35 @@
+use std::sync::LazyLock;
+
+use cosmrs::Any;
+use prost_reflect::{DescriptorPool, DynamicMessage};
+use serde_json::Value;
+
+use crate::{FILE_DESCRIPTOR_SET, ProtosError};
+
+static DESCRIPTOR_POOL: LazyLock<DescriptorPool> = LazyLock::new(|| {
+	DescriptorPool::decode(FILE_DESCRIPTOR_SET).expect("embedded file descriptor set must be valid")
+});
+
+pub fn descriptor_pool() -> &'static DescriptorPool {
+	&DESCRIPTOR_POOL
+}
+
+// decodes the msg into its canonical proto3 JSON, tagged with its type url under `@type` as is
+// done for `google.protobuf.Any`
+pub fn any_to_json(msg: &Any) -> Result<Value, ProtosError> {
+	let type_name = msg.type_url.rsplit_once('/').map_or(msg.type_url.as_str(), |(_, name)| name);
+
+	let descriptor = descriptor_pool()
+		.get_message_by_name(type_name)
+		.ok_or_else(|| ProtosError::UnknownMsg { type_url: msg.type_url.clone() })?;
+
+	let mut json = DynamicMessage::decode(descriptor, msg.value.as_slice())
+		.map_err(ProtosError::from)
+		.and_then(|msg| serde_json::to_value(&msg).map_err(From::from))?;
+
+	if let Value::Object(fields) = &mut json {
+		fields.insert("@type".into(), Value::String(msg.type_url.clone()));
+	}
+
+	Ok(json)
+}
@@ -1,11 +1,22 @@
 #[cfg(feature = "protogen")]
 mod error;
 
+#[cfg(feature = "json")]
+mod json;
+
 pub use imbibe_macros::GetSigners;
 
 #[cfg(feature = "protogen")]
 pub use self::{codegen::*, error::ProtosError, signer_extractor::*};
 
+#[cfg(feature = "json")]
+pub use self::json::{any_to_json, descriptor_pool};
+
+// encoded `google.protobuf.FileDescriptorSet` of all the compiled protobuf files
+#[cfg(feature = "protogen")]
+pub const FILE_DESCRIPTOR_SET: &[u8] =
+	include_bytes!(concat!(env!("OUT_DIR"), "/file_descriptor_set.bin"));
+
 #[allow(clippy::doc_overindented_list_items, clippy::doc_lazy_continuation)]
 mod codegen {
 	#[cfg(feature = "protogen")]
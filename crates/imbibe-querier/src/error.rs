@@ -1,3 +1,4 @@
+use imbibe_domain::AddressError;
 use imbibe_persistence::{pool::PoolError, store::StoreError};
 
 #[derive(Debug, thiserror::Error)]
@@ -7,4 +8,16 @@ pub enum QuerierError {
 
 	#[error("db pool error: {0}")]
 	DbPool(#[from] PoolError),
+
+	#[error("lagged error: subscriber skipped {0} live blocks")]
+	Lagged(u64),
+
+	#[error("address error: {0}")]
+	Address(#[from] AddressError),
+}
+
+impl QuerierError {
+	pub fn is_not_found(&self) -> bool {
+		matches!(self, Self::Store(e) if e.is_not_found())
+	}
 }
@@ -0,0 +1,56 @@
+mod loader;
+mod object;
+
+pub use self::object::QueryRoot;
+
+use async_graphql::{
+	EmptyMutation, EmptySubscription, Schema, dataloader::DataLoader, http::GraphiQLSource,
+};
+use axum::{
+	Json, Router,
+	extract::State,
+	response::{Html, IntoResponse},
+	routing::get,
+};
+
+use crate::server::Querier;
+
+use self::loader::{BlockLoader, TxsLoader};
+
+pub type QuerySchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+const MAX_DEPTH: usize = 16;
+
+const MAX_COMPLEXITY: usize = 1000;
+
+pub fn schema(querier: Querier) -> QuerySchema {
+	Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+		.data(DataLoader::new(
+			BlockLoader::new(querier.clone()),
+			tokio::spawn,
+		))
+		.data(DataLoader::new(
+			TxsLoader::new(querier.clone()),
+			tokio::spawn,
+		))
+		.data(querier)
+		.limit_depth(MAX_DEPTH)
+		.limit_complexity(MAX_COMPLEXITY)
+		.finish()
+}
+
+// serves the queries on `POST /graphql`, and the GraphiQL explorer on `GET /graphql`
+pub fn router(querier: Querier) -> Router {
+	Router::new().route("/graphql", get(graphiql).post(graphql)).with_state(schema(querier))
+}
+
+async fn graphql(
+	State(schema): State<QuerySchema>,
+	Json(request): Json<async_graphql::Request>,
+) -> Json<async_graphql::Response> {
+	Json(schema.execute(request).await)
+}
+
+async fn graphiql() -> impl IntoResponse {
+	Html(GraphiQLSource::build().endpoint("/graphql").finish())
+}
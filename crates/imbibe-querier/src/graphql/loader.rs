@@ -0,0 +1,64 @@
+use core::num::NonZeroU64;
+
+use std::{collections::HashMap, sync::Arc};
+
+use async_graphql::dataloader::Loader;
+use imbibe_domain::{block::Block, tx::Tx};
+
+use crate::{QuerierError, server::Querier};
+
+// batches the loads of the blocks by height
+pub struct BlockLoader {
+	querier: Querier,
+}
+
+// batches the loads of the txs of the blocks by block height
+pub struct TxsLoader {
+	querier: Querier,
+}
+
+impl BlockLoader {
+	pub fn new(querier: Querier) -> Self {
+		Self { querier }
+	}
+}
+
+impl TxsLoader {
+	pub fn new(querier: Querier) -> Self {
+		Self { querier }
+	}
+}
+
+impl Loader<NonZeroU64> for BlockLoader {
+	type Value = Block;
+	type Error = Arc<QuerierError>;
+
+	async fn load(
+		&self,
+		heights: &[NonZeroU64],
+	) -> Result<HashMap<NonZeroU64, Block>, Self::Error> {
+		let blocks = self.querier.get_blocks_by_heights(heights).await?;
+
+		Ok(blocks
+			.into_iter()
+			.filter_map(|block| NonZeroU64::new(block.header().height()).map(|h| (h, block)))
+			.collect())
+	}
+}
+
+impl Loader<NonZeroU64> for TxsLoader {
+	type Value = Vec<Tx>;
+	type Error = Arc<QuerierError>;
+
+	async fn load(
+		&self,
+		heights: &[NonZeroU64],
+	) -> Result<HashMap<NonZeroU64, Vec<Tx>>, Self::Error> {
+		let txs = self.querier.get_txs_by_block_heights(heights).await?;
+
+		Ok(txs.into_iter().fold(HashMap::new(), |mut txs, tx| {
+			txs.entry(tx.block_height()).or_insert_with(Vec::new).push(tx);
+			txs
+		}))
+	}
+}
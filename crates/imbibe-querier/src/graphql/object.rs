@@ -0,0 +1,339 @@
+use core::num::NonZeroU64;
+
+use async_graphql::{Context, Error, Object, Result, SimpleObject, dataloader::DataLoader};
+use imbibe_domain::{Address, AddressKind, Sha256, block, page::TxCursor, tx};
+
+use crate::{QuerierError, server::Querier};
+
+use super::loader::{BlockLoader, TxsLoader};
+
+pub struct QueryRoot;
+
+pub struct Block(block::Block);
+
+pub struct Tx(tx::Tx);
+
+pub struct Account(Address);
+
+#[derive(SimpleObject)]
+pub struct Msg {
+	type_url: String,
+	// hex encoded protobuf bytes of the msg
+	value: String,
+	// canonical proto3 JSON of the msg, if the indexer was able to decode it
+	json: Option<String>,
+}
+
+#[derive(SimpleObject)]
+pub struct Coin {
+	denom: String,
+	amount: String,
+}
+
+#[derive(SimpleObject)]
+pub struct TxPage {
+	items: Vec<Tx>,
+	next_cursor: Option<TxPageCursor>,
+}
+
+#[derive(SimpleObject)]
+pub struct TxPageCursor {
+	block_height: u64,
+	tx_idx_in_block: u64,
+}
+
+#[Object]
+impl QueryRoot {
+	async fn block(&self, ctx: &Context<'_>, height: u64) -> Result<Option<Block>> {
+		let Some(height) = NonZeroU64::new(height) else {
+			return Err(Error::new("height must be positive"));
+		};
+
+		load_block(ctx, height).await
+	}
+
+	async fn block_by_hash(&self, ctx: &Context<'_>, hash: String) -> Result<Option<Block>> {
+		let hash = hash.parse::<Sha256>()?;
+
+		ctx.data::<Querier>()?
+			.get_block_by_block_hash(&hash)
+			.await
+			.map(Block)
+			.map(Some)
+			.or_else(not_found_as_none)
+	}
+
+	async fn tx(&self, ctx: &Context<'_>, hash: String) -> Result<Option<Tx>> {
+		let hash = hash.parse::<Sha256>()?;
+
+		ctx.data::<Querier>()?
+			.get_tx_by_tx_hash(&hash)
+			.await
+			.map(Tx)
+			.map(Some)
+			.or_else(not_found_as_none)
+	}
+
+	async fn tx_by_position(
+		&self,
+		ctx: &Context<'_>,
+		block_height: u64,
+		tx_idx_in_block: u64,
+	) -> Result<Option<Tx>> {
+		let Some(block_height) = NonZeroU64::new(block_height) else {
+			return Err(Error::new("block height must be positive"));
+		};
+
+		ctx.data::<Querier>()?
+			.get_tx_by_block_height_and_tx_idx_in_block(block_height, tx_idx_in_block)
+			.await
+			.map(Tx)
+			.map(Some)
+			.or_else(not_found_as_none)
+	}
+
+	// accepts a hex or a bech32 address, of the chain prefix if configured
+	async fn account(&self, ctx: &Context<'_>, address: String) -> Result<Account> {
+		ctx.data::<Querier>()?
+			.parse_address(&address, AddressKind::Account)
+			.map(Account)
+			.map_err(From::from)
+	}
+
+	async fn indexed_height(&self, ctx: &Context<'_>, chain_id: String) -> Result<Option<u64>> {
+		ctx.data::<Querier>()?
+			.get_indexed_height(&chain_id)
+			.await
+			.map(|height| height.map(NonZeroU64::get))
+			.map_err(internal_error)
+	}
+}
+
+#[Object]
+impl Block {
+	async fn height(&self) -> u64 {
+		self.0.header().height()
+	}
+
+	async fn hash(&self) -> String {
+		self.0.hash().to_string()
+	}
+
+	async fn chain_id(&self) -> &str {
+		self.0.header().chain_id()
+	}
+
+	// RFC 3339 timestamp of the block
+	async fn time(&self) -> String {
+		self.0.header().time().to_string()
+	}
+
+	async fn proposer(&self, ctx: &Context<'_>) -> Result<String> {
+		ctx.data::<Querier>()?
+			.render_address(self.0.header().proposer(), AddressKind::ValidatorConsensus)
+			.map_err(From::from)
+	}
+
+	async fn app_hash(&self) -> String {
+		self.0.header().app_hash().to_string()
+	}
+
+	async fn gas_used(&self) -> u64 {
+		self.0.gas_used()
+	}
+
+	async fn txs(&self, ctx: &Context<'_>) -> Result<Vec<Tx>> {
+		let Some(height) = NonZeroU64::new(self.0.header().height()) else {
+			return Ok(vec![]);
+		};
+
+		ctx.data::<DataLoader<TxsLoader>>()?
+			.load_one(height)
+			.await
+			.map(|txs| txs.unwrap_or_default().into_iter().map(Tx).collect())
+			.map_err(|e| internal_error(&*e))
+	}
+}
+
+#[Object]
+impl Tx {
+	async fn block_height(&self) -> u64 {
+		self.0.block_height().get()
+	}
+
+	async fn tx_idx_in_block(&self) -> u64 {
+		self.0.tx_idx_in_block()
+	}
+
+	async fn hash(&self) -> String {
+		self.0.tx_hash().to_string()
+	}
+
+	async fn block(&self, ctx: &Context<'_>) -> Result<Option<Block>> {
+		load_block(ctx, self.0.block_height()).await
+	}
+
+	async fn msgs(&self) -> Vec<Msg> {
+		self.0
+			.msgs()
+			.get()
+			.iter()
+			.zip(self.0.msgs().json())
+			.map(|(msg, json)| Msg {
+				type_url: msg.type_url.clone(),
+				value: const_hex::encode_upper(&msg.value),
+				json: json.clone(),
+			})
+			.collect()
+	}
+
+	async fn fees(&self) -> Vec<Coin> {
+		self.0
+			.fees()
+			.map(|fees| fees.get())
+			.unwrap_or_default()
+			.iter()
+			.map(|coin| Coin { denom: coin.denom.to_string(), amount: coin.amount.to_string() })
+			.collect()
+	}
+
+	// hex encoded signatures
+	async fn signatures(&self) -> Vec<String> {
+		self.0.signatures().iter().map(const_hex::encode_upper).collect()
+	}
+
+	async fn memo(&self) -> Option<&str> {
+		self.0.memo().map(AsRef::as_ref)
+	}
+
+	async fn payer(&self, ctx: &Context<'_>) -> Result<String> {
+		ctx.data::<Querier>()?
+			.render_address(self.0.payer(), AddressKind::Account)
+			.map_err(From::from)
+	}
+
+	async fn granter(&self, ctx: &Context<'_>) -> Result<Option<String>> {
+		let querier = ctx.data::<Querier>()?;
+
+		self.0
+			.granter()
+			.map(|granter| querier.render_address(granter, AddressKind::Account))
+			.transpose()
+			.map_err(From::from)
+	}
+
+	async fn accounts(&self, ctx: &Context<'_>) -> Result<Vec<String>> {
+		let querier = ctx.data::<Querier>()?;
+
+		self.0
+			.accounts()
+			.iter()
+			.map(|account| querier.render_address(account, AddressKind::Account))
+			.collect::<Result<_, _>>()
+			.map_err(From::from)
+	}
+
+	async fn code(&self) -> u32 {
+		self.0.code().value()
+	}
+
+	async fn codespace(&self) -> Option<&str> {
+		self.0.codespace().map(AsRef::as_ref)
+	}
+
+	async fn log(&self) -> Option<&str> {
+		self.0.log().map(AsRef::as_ref)
+	}
+
+	async fn info(&self) -> Option<&str> {
+		self.0.info().map(AsRef::as_ref)
+	}
+
+	async fn gas_limit(&self) -> u64 {
+		self.0.gas_limit()
+	}
+
+	async fn gas_wanted(&self) -> u64 {
+		self.0.gas_wanted()
+	}
+
+	async fn gas_used(&self) -> u64 {
+		self.0.gas_used()
+	}
+}
+
+#[Object]
+impl Account {
+	async fn address(&self, ctx: &Context<'_>) -> Result<String> {
+		ctx.data::<Querier>()?.render_address(&self.0, AddressKind::Account).map_err(From::from)
+	}
+
+	// txs the account is involved in, latest first; the limit is capped by the querier. As the
+	// pages of the accounts are not batched, each tx of the page counts towards the complexity
+	#[graphql(complexity = "limit.min(Querier::MAX_PAGE_LIMIT.get()) as usize * child_complexity")]
+	async fn txs(
+		&self,
+		ctx: &Context<'_>,
+		before: Option<TxPageCursorInput>,
+		#[graphql(default = 10)] limit: u64,
+	) -> Result<TxPage> {
+		let Some(limit) = NonZeroU64::new(limit) else {
+			return Err(Error::new("limit must be positive"));
+		};
+
+		let cursor = before.map(TxCursor::try_from).transpose()?;
+		let page = ctx
+			.data::<Querier>()?
+			.get_txs_by_address(&self.0, cursor, limit)
+			.await
+			.map_err(internal_error)?;
+
+		let next_cursor = page.next_cursor().map(|cursor| TxPageCursor {
+			block_height: cursor.block_height().get(),
+			tx_idx_in_block: cursor.tx_idx_in_block(),
+		});
+
+		Ok(TxPage { items: page.into_items().into_iter().map(Tx).collect(), next_cursor })
+	}
+}
+
+#[derive(async_graphql::InputObject)]
+pub struct TxPageCursorInput {
+	block_height: u64,
+	tx_idx_in_block: u64,
+}
+
+impl TryFrom<TxPageCursorInput> for TxCursor {
+	type Error = Error;
+
+	fn try_from(input: TxPageCursorInput) -> Result<Self, Self::Error> {
+		let block_height = NonZeroU64::new(input.block_height)
+			.ok_or_else(|| Error::new("block height must be positive"))?;
+
+		Ok(Self::builder()
+			.block_height(block_height)
+			.tx_idx_in_block(input.tx_idx_in_block)
+			.build())
+	}
+}
+
+async fn load_block(ctx: &Context<'_>, height: NonZeroU64) -> Result<Option<Block>> {
+	ctx.data::<DataLoader<BlockLoader>>()?
+		.load_one(height)
+		.await
+		.map(|block| block.map(Block))
+		.map_err(|e| internal_error(&*e))
+}
+
+fn not_found_as_none<T>(err: QuerierError) -> Result<Option<T>> {
+	if err.is_not_found() {
+		Ok(None)
+	} else {
+		Err(internal_error(&err))
+	}
+}
+
+// the internal errors are already logged by the querier, and are not leaked to the clients
+fn internal_error<E>(_: E) -> Error {
+	Error::new("internal error")
+}
@@ -0,0 +1,10 @@
+#[cfg(feature = "server")]
+pub mod server;
+
+#[cfg(feature = "server")]
+mod convert;
+
+// generated by imbibe-protos from its `proto/imbibe/query/v1/query.proto`
+pub use imbibe_protos::imbibe::query::v1 as proto;
+
+pub use self::proto::query_client::QueryClient;
@@ -0,0 +1,106 @@
+use core::num::NonZeroU64;
+
+use imbibe_domain::{
+	block::{Block, Header},
+	page::{Page, TxCursor},
+	tx::Tx,
+};
+
+use super::proto;
+
+// plain functions rather than `From` impls, as the proto types are generated by imbibe-protos
+pub fn block(block: &Block) -> proto::Block {
+	proto::Block {
+		header: Some(header(block.header())),
+		gas_used: block.gas_used(),
+		hash: block.hash().get().to_vec(),
+		data: block.data().get().iter().map(|bz| bz.get().to_vec()).collect(),
+	}
+}
+
+pub fn header(header: &Header) -> proto::Header {
+	proto::Header {
+		chain_id: header.chain_id().to_owned(),
+		height: header.height(),
+		time: Some(prost_types::Timestamp {
+			seconds: header.time().as_second(),
+			nanos: header.time().subsec_nanosecond(),
+		}),
+		validators_hash: header.validators_hash().get().to_vec(),
+		next_validators_hash: header.next_validators_hash().get().to_vec(),
+		consensus_hash: header.consensus_hash().get().to_vec(),
+		app_hash: header.app_hash().get().to_vec(),
+		proposer: header.proposer().as_bytes().to_vec(),
+		last_commit_hash: header.last_commit_hash().map(|h| h.get().to_vec()),
+		data_hash: header.data_hash().map(|h| h.get().to_vec()),
+		last_results_hash: header.last_results_hash().map(|h| h.get().to_vec()),
+		evidence_hash: header.evidence_hash().map(|h| h.get().to_vec()),
+	}
+}
+
+pub fn tx(tx: &Tx) -> proto::Tx {
+	proto::Tx {
+		block_height: tx.block_height().get(),
+		tx_idx_in_block: tx.tx_idx_in_block(),
+		tx_hash: tx.tx_hash().get().to_vec(),
+		msgs: tx
+			.msgs()
+			.get()
+			.iter()
+			.zip(tx.msgs().json())
+			.map(|(msg, json)| proto::Msg {
+				msg: Some(prost_types::Any {
+					type_url: msg.type_url.clone(),
+					value: msg.value.clone(),
+				}),
+				json: json.clone(),
+			})
+			.collect(),
+		memo: tx.memo().map(|memo| memo.as_ref().to_owned()),
+		timeout_height: tx.timeout_height().map(NonZeroU64::get),
+		signatures: tx.signatures().to_vec(),
+		signers: tx
+			.signers()
+			.iter()
+			.cloned()
+			.map(cosmrs::Any::from)
+			.map(|any| prost_types::Any { type_url: any.type_url, value: any.value })
+			.collect(),
+		fees: tx
+			.fees()
+			.map(|fees| fees.get())
+			.unwrap_or_default()
+			.iter()
+			.map(|coin| proto::Coin {
+				denom: coin.denom.to_string(),
+				amount: coin.amount.to_string(),
+			})
+			.collect(),
+		payer: tx.payer().as_bytes().to_vec(),
+		granter: tx.granter().map(|granter| granter.as_bytes().to_vec()),
+		accounts: tx.accounts().iter().map(|account| account.as_bytes().to_vec()).collect(),
+		code: tx.code().value(),
+		codespace: tx.codespace().map(|codespace| codespace.as_ref().to_owned()),
+		log: tx.log().map(|log| log.as_ref().to_owned()),
+		info: tx.info().map(|info| info.as_ref().to_owned()),
+		gas_limit: tx.gas_limit(),
+		gas_wanted: tx.gas_wanted(),
+		gas_used: tx.gas_used(),
+		data: tx.data_bz().map(|bz| bz.get().to_vec()),
+		tx_bz: tx.tx_bz().get().to_vec(),
+	}
+}
+
+pub fn tx_cursor(cursor: &TxCursor) -> proto::TxCursor {
+	proto::TxCursor {
+		block_height: cursor.block_height().get(),
+		tx_idx_in_block: cursor.tx_idx_in_block(),
+	}
+}
+
+pub fn txs_page(page: &Page<Tx, TxCursor>) -> proto::TxsByAddressResponse {
+	proto::TxsByAddressResponse {
+		txs: page.items().iter().map(tx).collect(),
+		next_cursor: page.next_cursor().map(tx_cursor),
+	}
+}
@@ -0,0 +1,179 @@
+use core::num::NonZeroU64;
+
+use bon::Builder;
+use futures::{StreamExt, TryStreamExt, stream::BoxStream};
+use imbibe_domain::{Address, Sha256, filter::TxSubscriptionFilter, page::TxCursor};
+use tonic::{Request, Response, Status};
+
+use crate::{QuerierError, server::Querier};
+
+use super::{
+	convert,
+	proto::{self, query_server::Query},
+};
+
+pub use super::proto::query_server::QueryServer;
+
+#[derive(Clone, Builder)]
+pub struct QueryGrpcServer {
+	querier: Querier,
+}
+
+#[tonic::async_trait]
+impl Query for QueryGrpcServer {
+	async fn block_by_height(
+		&self,
+		request: Request<proto::BlockByHeightRequest>,
+	) -> Result<Response<proto::BlockResponse>, Status> {
+		let height = NonZeroU64::new(request.into_inner().height).ok_or_else(invalid_height)?;
+		let block = self.querier.get_block_by_height(height).await?;
+
+		Ok(Response::new(proto::BlockResponse {
+			block: Some(convert::block(&block)),
+		}))
+	}
+
+	async fn block_by_block_hash(
+		&self,
+		request: Request<proto::BlockByBlockHashRequest>,
+	) -> Result<Response<proto::BlockResponse>, Status> {
+		let block_hash = parse_hash(&request.into_inner().block_hash).ok_or_else(invalid_hash)?;
+		let block = self.querier.get_block_by_block_hash(&block_hash).await?;
+
+		Ok(Response::new(proto::BlockResponse {
+			block: Some(convert::block(&block)),
+		}))
+	}
+
+	async fn tx_by_block_height_and_tx_idx_in_block(
+		&self,
+		request: Request<proto::TxByBlockHeightAndTxIdxInBlockRequest>,
+	) -> Result<Response<proto::TxResponse>, Status> {
+		let request = request.into_inner();
+		let height = NonZeroU64::new(request.height).ok_or_else(invalid_height)?;
+		let tx = self
+			.querier
+			.get_tx_by_block_height_and_tx_idx_in_block(height, request.tx_idx_in_block)
+			.await?;
+
+		Ok(Response::new(proto::TxResponse {
+			tx: Some(convert::tx(&tx)),
+		}))
+	}
+
+	async fn tx_by_tx_hash(
+		&self,
+		request: Request<proto::TxByTxHashRequest>,
+	) -> Result<Response<proto::TxResponse>, Status> {
+		let tx_hash = parse_hash(&request.into_inner().tx_hash).ok_or_else(invalid_hash)?;
+		let tx = self.querier.get_tx_by_tx_hash(&tx_hash).await?;
+
+		Ok(Response::new(proto::TxResponse {
+			tx: Some(convert::tx(&tx)),
+		}))
+	}
+
+	async fn indexed_height(
+		&self,
+		request: Request<proto::IndexedHeightRequest>,
+	) -> Result<Response<proto::IndexedHeightResponse>, Status> {
+		let height = self.querier.get_indexed_height(&request.into_inner().chain_id).await?;
+
+		Ok(Response::new(proto::IndexedHeightResponse {
+			height: height.map(NonZeroU64::get),
+		}))
+	}
+
+	async fn txs_by_address(
+		&self,
+		request: Request<proto::TxsByAddressRequest>,
+	) -> Result<Response<proto::TxsByAddressResponse>, Status> {
+		let request = request.into_inner();
+		let address = Address::try_from(request.address.as_slice())
+			.map_err(|e| Status::invalid_argument(e.to_string()))?;
+		let cursor = match request.cursor {
+			Some(cursor) => Some(
+				TxCursor::builder()
+					.block_height(NonZeroU64::new(cursor.block_height).ok_or_else(invalid_height)?)
+					.tx_idx_in_block(cursor.tx_idx_in_block)
+					.build(),
+			),
+			None => None,
+		};
+		let limit = NonZeroU64::new(request.limit)
+			.ok_or_else(|| Status::invalid_argument("limit must be positive"))?;
+
+		let page = self.querier.get_txs_by_address(&address, cursor, limit).await?;
+
+		Ok(Response::new(convert::txs_page(&page)))
+	}
+
+	type SubscribeBlocksStream = BoxStream<'static, Result<proto::BlockResponse, Status>>;
+
+	async fn subscribe_blocks(
+		&self,
+		_: Request<proto::SubscribeBlocksRequest>,
+	) -> Result<Response<Self::SubscribeBlocksStream>, Status> {
+		let blocks = self
+			.querier
+			.subscribe_blocks()
+			.map_ok(|block| proto::BlockResponse { block: Some(convert::block(&block)) })
+			.map_err(From::from);
+
+		Ok(Response::new(blocks.boxed()))
+	}
+
+	type SubscribeTxsStream = BoxStream<'static, Result<proto::TxResponse, Status>>;
+
+	async fn subscribe_txs(
+		&self,
+		request: Request<proto::SubscribeTxsRequest>,
+	) -> Result<Response<Self::SubscribeTxsStream>, Status> {
+		let request = request.into_inner();
+		let address = request
+			.address
+			.as_deref()
+			.map(Address::try_from)
+			.transpose()
+			.map_err(|e| Status::invalid_argument(e.to_string()))?;
+		let filter = TxSubscriptionFilter::builder()
+			.maybe_type_url(request.type_url)
+			.maybe_address(address)
+			.build();
+
+		let txs = self
+			.querier
+			.subscribe_txs(filter)
+			.map_ok(|tx| proto::TxResponse { tx: Some(convert::tx(&tx)) })
+			.map_err(From::from);
+
+		Ok(Response::new(txs.boxed()))
+	}
+}
+
+// the internal errors are already logged by the querier, and are not leaked to the clients
+impl From<QuerierError> for Status {
+	fn from(err: QuerierError) -> Self {
+		if err.is_not_found() {
+			Status::not_found("not found")
+		} else if let QuerierError::Lagged(_) = err {
+			Status::data_loss(err.to_string())
+		} else if let QuerierError::Address(_) = err {
+			Status::invalid_argument(err.to_string())
+		} else {
+			Status::internal("internal error")
+		}
+	}
+}
+
+fn parse_hash(hash: &[u8]) -> Option<Sha256> {
+	hash.try_into().map(Sha256::new).ok()
+}
+
+fn invalid_height() -> Status {
+	Status::invalid_argument("height must be positive")
+}
+
+fn invalid_hash() -> Status {
+	Status::invalid_argument(format!("hash must be exactly {} bytes long", Sha256::LEN))
+}
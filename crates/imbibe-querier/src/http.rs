@@ -0,0 +1,227 @@
+mod error;
+
+pub use self::error::QueryHttpError;
+
+use core::num::NonZeroU64;
+
+use axum::{
+	Json, Router,
+	extract::{Path, Query, State},
+	response::sse::{Event, KeepAlive, Sse},
+	routing::get,
+};
+use futures::{Stream, StreamExt};
+use imbibe_domain::{
+	AddressError, AddressKind, Bech32Prefixes, Sha256, block::Block, filter::TxSubscriptionFilter,
+	tx::Tx,
+};
+use serde::{Deserialize, Serialize, Serializer, ser};
+use serde_json::Value;
+
+use crate::{QuerierError, server::Querier};
+
+#[derive(Deserialize)]
+struct TxSubscriptionParams {
+	type_url: Option<String>,
+	address: Option<String>,
+}
+
+// hashes in the paths are hex strings of either case
+#[derive(Deserialize)]
+#[serde(transparent)]
+struct HexHash(#[serde(with = "imbibe_domain::hex::upper")] Sha256);
+
+// the item, with its hashes rendered as upper case hex strings, and its addresses rendered as
+// bech32 strings of the chain prefixes if the querier has them, and as hex strings otherwise
+struct HttpJson<T> {
+	item: T,
+	prefixes: Option<Bech32Prefixes>,
+}
+
+trait Render {
+	// replaces the hashes in the JSON of the item, serialized as byte arrays, with their hex strings
+	fn hashes_to_hex(&self, json: &mut Value);
+
+	// replaces the addresses in the JSON of the item with their bech32 strings
+	fn addresses_to_bech32(
+		&self,
+		json: &mut Value,
+		prefixes: &Bech32Prefixes,
+	) -> Result<(), AddressError>;
+}
+
+pub fn router(querier: Querier) -> Router {
+	Router::new()
+		.route("/blocks/{height}", get(block_by_height))
+		.route("/blocks/hash/{block_hash}", get(block_by_block_hash))
+		.route(
+			"/blocks/{height}/txs/{tx_idx_in_block}",
+			get(tx_by_block_height_and_tx_idx_in_block),
+		)
+		.route("/txs/{tx_hash}", get(tx_by_tx_hash))
+		.route("/subscribe/blocks", get(subscribe_blocks))
+		.route("/subscribe/txs", get(subscribe_txs))
+		.with_state(querier)
+}
+
+async fn block_by_height(
+	State(querier): State<Querier>,
+	Path(height): Path<NonZeroU64>,
+) -> Result<Json<HttpJson<Block>>, QueryHttpError> {
+	querier
+		.get_block_by_height(height)
+		.await
+		.map(|block| Json(HttpJson::new(&querier, block)))
+		.map_err(From::from)
+}
+
+async fn block_by_block_hash(
+	State(querier): State<Querier>,
+	Path(HexHash(block_hash)): Path<HexHash>,
+) -> Result<Json<HttpJson<Block>>, QueryHttpError> {
+	querier
+		.get_block_by_block_hash(&block_hash)
+		.await
+		.map(|block| Json(HttpJson::new(&querier, block)))
+		.map_err(From::from)
+}
+
+async fn tx_by_block_height_and_tx_idx_in_block(
+	State(querier): State<Querier>,
+	Path((height, tx_idx_in_block)): Path<(NonZeroU64, u64)>,
+) -> Result<Json<HttpJson<Tx>>, QueryHttpError> {
+	querier
+		.get_tx_by_block_height_and_tx_idx_in_block(height, tx_idx_in_block)
+		.await
+		.map(|tx| Json(HttpJson::new(&querier, tx)))
+		.map_err(From::from)
+}
+
+async fn tx_by_tx_hash(
+	State(querier): State<Querier>,
+	Path(HexHash(tx_hash)): Path<HexHash>,
+) -> Result<Json<HttpJson<Tx>>, QueryHttpError> {
+	querier
+		.get_tx_by_tx_hash(&tx_hash)
+		.await
+		.map(|tx| Json(HttpJson::new(&querier, tx)))
+		.map_err(From::from)
+}
+
+async fn subscribe_blocks(
+	State(querier): State<Querier>,
+) -> Sse<impl Stream<Item = Result<Event, axum::Error>>> {
+	let blocks = querier.subscribe_blocks();
+
+	Sse::new(blocks.map(move |block| sse_event(&querier, "block", block)))
+		.keep_alive(KeepAlive::default())
+}
+
+async fn subscribe_txs(
+	State(querier): State<Querier>,
+	Query(TxSubscriptionParams { type_url, address }): Query<TxSubscriptionParams>,
+) -> Result<Sse<impl Stream<Item = Result<Event, axum::Error>>>, QueryHttpError> {
+	let address =
+		address.map(|address| querier.parse_address(&address, AddressKind::Account)).transpose()?;
+	let filter =
+		TxSubscriptionFilter::builder().maybe_type_url(type_url).maybe_address(address).build();
+	let txs = querier.subscribe_txs(filter);
+
+	Ok(Sse::new(txs.map(move |tx| sse_event(&querier, "tx", tx))).keep_alive(KeepAlive::default()))
+}
+
+// a subscription error is sent as an `error` event, after which the stream ends
+fn sse_event<T>(
+	querier: &Querier,
+	kind: &'static str,
+	item: Result<T, QuerierError>,
+) -> Result<Event, axum::Error>
+where
+	T: Serialize + Render,
+{
+	match item {
+		Ok(item) => Event::default().event(kind).json_data(HttpJson::new(querier, item)),
+		Err(e) => Ok(Event::default().event("error").data(e.to_string())),
+	}
+}
+
+impl<T> HttpJson<T> {
+	fn new(querier: &Querier, item: T) -> Self {
+		Self { item, prefixes: querier.bech32_prefixes().cloned() }
+	}
+}
+
+impl<T> Serialize for HttpJson<T>
+where
+	T: Serialize + Render,
+{
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		let mut json = serde_json::to_value(&self.item).map_err(ser::Error::custom)?;
+		self.item.hashes_to_hex(&mut json);
+
+		if let Some(prefixes) = &self.prefixes {
+			self.item.addresses_to_bech32(&mut json, prefixes).map_err(ser::Error::custom)?;
+		}
+
+		json.serialize(serializer)
+	}
+}
+
+impl Render for Block {
+	fn hashes_to_hex(&self, json: &mut Value) {
+		let header = self.header();
+
+		json["hash"] = self.hash().to_string().into();
+		json["header"]["validators_hash"] = header.validators_hash().to_string().into();
+		json["header"]["next_validators_hash"] = header.next_validators_hash().to_string().into();
+		json["header"]["consensus_hash"] = header.consensus_hash().to_string().into();
+		json["header"]["app_hash"] = header.app_hash().to_string().into();
+		json["header"]["last_commit_hash"] =
+			header.last_commit_hash().map(ToString::to_string).into();
+		json["header"]["data_hash"] = header.data_hash().map(ToString::to_string).into();
+		json["header"]["last_results_hash"] =
+			header.last_results_hash().map(ToString::to_string).into();
+		json["header"]["evidence_hash"] = header.evidence_hash().map(ToString::to_string).into();
+	}
+
+	fn addresses_to_bech32(
+		&self,
+		json: &mut Value,
+		prefixes: &Bech32Prefixes,
+	) -> Result<(), AddressError> {
+		json["header"]["proposer"] =
+			prefixes.encode(self.header().proposer(), AddressKind::ValidatorConsensus)?.into();
+
+		Ok(())
+	}
+}
+
+impl Render for Tx {
+	fn hashes_to_hex(&self, json: &mut Value) {
+		json["tx_hash"] = self.tx_hash().to_string().into();
+	}
+
+	fn addresses_to_bech32(
+		&self,
+		json: &mut Value,
+		prefixes: &Bech32Prefixes,
+	) -> Result<(), AddressError> {
+		json["payer"] = prefixes.encode(self.payer(), AddressKind::Account)?.into();
+
+		if let Some(granter) = self.granter() {
+			json["granter"] = prefixes.encode(granter, AddressKind::Account)?.into();
+		}
+
+		json["accounts"] = self
+			.accounts()
+			.iter()
+			.map(|account| prefixes.encode(account, AddressKind::Account))
+			.collect::<Result<Vec<_>, _>>()?
+			.into();
+
+		Ok(())
+	}
+}
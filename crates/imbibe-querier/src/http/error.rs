@@ -0,0 +1,35 @@
+use axum::{
+	Json,
+	http::StatusCode,
+	response::{IntoResponse, Response},
+};
+use serde::Serialize;
+
+use crate::QuerierError;
+
+#[derive(Debug, thiserror::Error)]
+#[error("{0}")]
+pub struct QueryHttpError(#[from] QuerierError);
+
+#[derive(Serialize)]
+struct ErrorBody {
+	error: String,
+}
+
+// the internal errors are already logged by the querier, and are not leaked to the clients
+impl IntoResponse for QueryHttpError {
+	fn into_response(self) -> Response {
+		let (status, error) = if self.0.is_not_found() {
+			(StatusCode::NOT_FOUND, "not found".into())
+		} else if let QuerierError::Address(e) = &self.0 {
+			(StatusCode::BAD_REQUEST, e.to_string())
+		} else {
+			(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				"internal server error".into(),
+			)
+		};
+
+		(status, Json(ErrorBody { error })).into_response()
+	}
+}
@@ -1,3 +1,12 @@
+#[cfg(feature = "graphql")]
+pub mod graphql;
+
+#[cfg(feature = "grpc")]
+pub mod grpc;
+
+#[cfg(feature = "http")]
+pub mod http;
+
 #[cfg(feature = "server")]
 pub mod server;
 
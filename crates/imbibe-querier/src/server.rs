@@ -1,16 +1,65 @@
-use core::num::NonZeroU64;
+mod feed;
+
+use core::{num::NonZeroU64, pin::pin};
 
 use bon::Builder;
-use imbibe_domain::{block::Block, tx::Tx};
+use futures::{Stream, StreamExt, TryStreamExt, stream};
+use imbibe_domain::{
+	Address, AddressKind, Bech32Prefixes,
+	block::{Block, TimeBound},
+	evidence::Evidence,
+	filter::{TxFilter, TxSubscriptionFilter},
+	page::{EvidenceCursor, HeightRange, Order, Page, TxCursor},
+	tx::Tx,
+	validator::{BlockSigner, ValidatorSet},
+};
+use jiff::Timestamp;
 
 use crate::QuerierError;
 
+use self::feed::Feed;
+
 #[derive(Clone, Builder)]
 pub struct Querier {
 	pool: imbibe_persistence::pool::DbPool,
+
+	// the bech32 prefixes of the chain, which the addresses are rendered with and checked against
+	bech32_prefixes: Option<Bech32Prefixes>,
+
+	#[builder(skip)]
+	feed: Feed,
 }
 
 impl Querier {
+	pub const MAX_PAGE_LIMIT: NonZeroU64 = NonZeroU64::new(100).expect("must be positive");
+
+	pub fn bech32_prefixes(&self) -> Option<&Bech32Prefixes> {
+		self.bech32_prefixes.as_ref()
+	}
+
+	// parses a hex address, or a bech32 address of the chain prefix of the kind if the prefixes are
+	// set, or else of any prefix
+	pub fn parse_address(&self, s: &str, kind: AddressKind) -> Result<Address, QuerierError> {
+		match &self.bech32_prefixes {
+			Some(prefixes) => prefixes.parse(s, kind),
+			None => s.parse(),
+		}
+		.map_err(From::from)
+	}
+
+	// renders the address as a bech32 string of the chain prefix of the kind if the prefixes are
+	// set, or else as a hex string
+	pub fn render_address(
+		&self,
+		address: &Address,
+		kind: AddressKind,
+	) -> Result<String, QuerierError> {
+		match &self.bech32_prefixes {
+			Some(prefixes) => prefixes.encode(address, kind).map_err(From::from),
+			None => Ok(address.to_string()),
+		}
+	}
+
 	pub async fn get_block_by_height(&self, height: NonZeroU64) -> Result<Block, QuerierError> {
 		imbibe_persistence::store::fetch_block_by_height(&mut self.pool.get().await?, height)
 			.await
@@ -31,6 +80,49 @@ impl Querier {
 		.inspect_err(|e| tracing::error!("{e}"))
 	}
 
+	// the block that was current at the time with `TimeBound::AtOrBefore`, or the first block at
+	// or after the time with `TimeBound::AtOrAfter`
+	pub async fn get_block_by_time(
+		&self,
+		time: &Timestamp,
+		bound: TimeBound,
+	) -> Result<Block, QuerierError> {
+		imbibe_persistence::store::fetch_block_by_time(&mut self.pool.get().await?, time, bound)
+			.await
+			.map_err(From::from)
+			.inspect_err(|e| tracing::error!("{e}"))
+	}
+
+	pub async fn get_blocks_by_heights(
+		&self,
+		heights: &[NonZeroU64],
+	) -> Result<Vec<Block>, QuerierError> {
+		imbibe_persistence::store::fetch_blocks_by_heights(&mut self.pool.get().await?, heights)
+			.await
+			.map_err(From::from)
+			.inspect_err(|e| tracing::error!("{e}"))
+	}
+
+	pub async fn get_txs_by_block_height(
+		&self,
+		height: NonZeroU64,
+	) -> Result<Vec<Tx>, QuerierError> {
+		imbibe_persistence::store::fetch_txs_by_block_height(&mut self.pool.get().await?, height)
+			.await
+			.map_err(From::from)
+			.inspect_err(|e| tracing::error!("{e}"))
+	}
+
+	pub async fn get_txs_by_block_heights(
+		&self,
+		heights: &[NonZeroU64],
+	) -> Result<Vec<Tx>, QuerierError> {
+		imbibe_persistence::store::fetch_txs_by_block_heights(&mut self.pool.get().await?, heights)
+			.await
+			.map_err(From::from)
+			.inspect_err(|e| tracing::error!("{e}"))
+	}
+
 	pub async fn get_tx_by_block_height_and_tx_idx_in_block(
 		&self,
 		height: core::num::NonZeroU64,
@@ -55,4 +147,214 @@ impl Querier {
 			.map_err(From::from)
 			.inspect_err(|e| tracing::error!("{e}"))
 	}
+
+	pub async fn get_indexed_height(
+		&self,
+		chain_id: &str,
+	) -> Result<Option<NonZeroU64>, QuerierError> {
+		imbibe_persistence::store::fetch_indexed_height(&mut self.pool.get().await?, chain_id)
+			.await
+			.map_err(From::from)
+			.inspect_err(|e| tracing::error!("{e}"))
+	}
+
+	// txs the account is involved in, latest first; the limit is capped at `MAX_PAGE_LIMIT`
+	pub async fn get_txs_by_address(
+		&self,
+		address: &Address,
+		cursor: Option<TxCursor>,
+		limit: NonZeroU64,
+	) -> Result<Page<Tx, TxCursor>, QuerierError> {
+		imbibe_persistence::store::fetch_txs_by_address(
+			&mut self.pool.get().await?,
+			address,
+			cursor,
+			limit.min(Self::MAX_PAGE_LIMIT),
+		)
+		.await
+		.map_err(From::from)
+		.inspect_err(|e| tracing::error!("{e}"))
+	}
+
+	// blocks in the range, continuing after the cursor in the given order; the limit is capped
+	// at `MAX_PAGE_LIMIT`
+	pub async fn get_blocks(
+		&self,
+		range: HeightRange,
+		cursor: Option<NonZeroU64>,
+		order: Order,
+		limit: NonZeroU64,
+	) -> Result<Page<Block, NonZeroU64>, QuerierError> {
+		imbibe_persistence::store::fetch_blocks(
+			&mut self.pool.get().await?,
+			range,
+			cursor,
+			order,
+			limit.min(Self::MAX_PAGE_LIMIT),
+		)
+		.await
+		.map_err(From::from)
+		.inspect_err(|e| tracing::error!("{e}"))
+	}
+
+	// txs of the blocks in the range, continuing after the cursor in the given order; the limit
+	// is capped at `MAX_PAGE_LIMIT`
+	pub async fn get_txs(
+		&self,
+		range: HeightRange,
+		cursor: Option<TxCursor>,
+		order: Order,
+		limit: NonZeroU64,
+	) -> Result<Page<Tx, TxCursor>, QuerierError> {
+		imbibe_persistence::store::fetch_txs(
+			&mut self.pool.get().await?,
+			range,
+			cursor,
+			order,
+			limit.min(Self::MAX_PAGE_LIMIT),
+		)
+		.await
+		.map_err(From::from)
+		.inspect_err(|e| tracing::error!("{e}"))
+	}
+
+	// txs matching the filter, continuing after the cursor in the given order; the limit is
+	// capped at `MAX_PAGE_LIMIT`
+	pub async fn search_txs(
+		&self,
+		filter: &TxFilter,
+		cursor: Option<TxCursor>,
+		order: Order,
+		limit: NonZeroU64,
+	) -> Result<Page<Tx, TxCursor>, QuerierError> {
+		imbibe_persistence::store::search_txs(
+			&mut self.pool.get().await?,
+			filter,
+			cursor,
+			order,
+			limit.min(Self::MAX_PAGE_LIMIT),
+		)
+		.await
+		.map_err(From::from)
+		.inspect_err(|e| tracing::error!("{e}"))
+	}
+
+	// validator set in charge of the block at the height
+	pub async fn get_validator_set(
+		&self,
+		height: NonZeroU64,
+	) -> Result<ValidatorSet, QuerierError> {
+		imbibe_persistence::store::fetch_validator_set(&mut self.pool.get().await?, height)
+			.await
+			.map_err(From::from)
+			.inspect_err(|e| tracing::error!("{e}"))
+	}
+
+	// validators of the block at the height along with their votes for it, available once the
+	// block above it is indexed
+	pub async fn get_block_signers(
+		&self,
+		height: NonZeroU64,
+	) -> Result<Vec<BlockSigner>, QuerierError> {
+		imbibe_persistence::store::fetch_block_signers(&mut self.pool.get().await?, height)
+			.await
+			.map_err(From::from)
+			.inspect_err(|e| tracing::error!("{e}"))
+	}
+
+	// heights of the blocks in the range the validator missed to vote for, continuing after the
+	// cursor in the given order; the limit is capped at `MAX_PAGE_LIMIT`
+	pub async fn get_missed_blocks(
+		&self,
+		address: &Address,
+		range: HeightRange,
+		cursor: Option<NonZeroU64>,
+		order: Order,
+		limit: NonZeroU64,
+	) -> Result<Page<NonZeroU64, NonZeroU64>, QuerierError> {
+		imbibe_persistence::store::fetch_missed_blocks(
+			&mut self.pool.get().await?,
+			address,
+			range,
+			cursor,
+			order,
+			limit.min(Self::MAX_PAGE_LIMIT),
+		)
+		.await
+		.map_err(From::from)
+		.inspect_err(|e| tracing::error!("{e}"))
+	}
+
+	// evidence committed in the blocks in the range, optionally only the ones the validator
+	// offended in, continuing after the cursor in the given order; the limit is capped at
+	// `MAX_PAGE_LIMIT`
+	pub async fn get_evidence(
+		&self,
+		range: HeightRange,
+		validator: Option<&Address>,
+		cursor: Option<EvidenceCursor>,
+		order: Order,
+		limit: NonZeroU64,
+	) -> Result<Page<Evidence, EvidenceCursor>, QuerierError> {
+		imbibe_persistence::store::fetch_evidence(
+			&mut self.pool.get().await?,
+			range,
+			validator,
+			cursor,
+			order,
+			limit.min(Self::MAX_PAGE_LIMIT),
+		)
+		.await
+		.map_err(From::from)
+		.inspect_err(|e| tracing::error!("{e}"))
+	}
+
+	// fetches the blocks at the heights as they get indexed, along with their txs, and pushes them
+	// to the subscribers; runs until the heights end
+	pub async fn feed<S>(&self, heights: S)
+	where
+		S: Stream<Item = NonZeroU64>,
+	{
+		let mut heights = pin!(heights);
+
+		while let Some(height) = heights.next().await {
+			if !self.feed.has_subscribers() {
+				continue;
+			}
+
+			// the errors are logged already, and the subscribers miss only the block
+			if let Ok((block, txs)) = futures::try_join!(
+				self.get_block_by_height(height),
+				self.get_txs_by_block_height(height),
+			) {
+				self.feed.publish(block, txs);
+			}
+		}
+	}
+
+	// the live blocks indexed from now on; the stream ends with `QuerierError::Lagged` if the
+	// subscriber falls behind
+	pub fn subscribe_blocks(
+		&self,
+	) -> impl Stream<Item = Result<Block, QuerierError>> + Send + use<> {
+		self.feed.subscribe().map_ok(|live_block| live_block.block.clone())
+	}
+
+	// the live txs matching the filter indexed from now on; the stream ends with
+	// `QuerierError::Lagged` if the subscriber falls behind
+	pub fn subscribe_txs(
+		&self,
+		filter: TxSubscriptionFilter,
+	) -> impl Stream<Item = Result<Tx, QuerierError>> + Send + use<> {
+		self.feed.subscribe().flat_map(move |live_block| {
+			let txs = match live_block {
+				Ok(live_block) => {
+					live_block.txs.iter().filter(|tx| filter.matches(tx)).cloned().map(Ok).collect()
+				},
+				Err(e) => vec![Err(e)],
+			};
+
+			stream::iter(txs)
+		})
+	}
 }
@@ -0,0 +1,52 @@
+use std::sync::Arc;
+
+use futures::{Stream, stream};
+use imbibe_domain::{block::Block, tx::Tx};
+use tokio::sync::broadcast::{self, error::RecvError};
+
+use crate::QuerierError;
+
+// number of the live blocks buffered for the subscribers, the subscribers lagging further behind
+// get dropped
+const CAPACITY: usize = 64;
+
+#[derive(Clone)]
+pub(super) struct Feed(broadcast::Sender<Arc<LiveBlock>>);
+
+pub(super) struct LiveBlock {
+	pub(super) block: Block,
+	pub(super) txs: Vec<Tx>,
+}
+
+impl Feed {
+	pub(super) fn has_subscribers(&self) -> bool {
+		self.0.receiver_count() > 0
+	}
+
+	pub(super) fn publish(&self, block: Block, txs: Vec<Tx>) {
+		// fails only when there are no subscribers
+		let _ = self.0.send(Arc::new(LiveBlock { block, txs }));
+	}
+
+	pub(super) fn subscribe(
+		&self,
+	) -> impl Stream<Item = Result<Arc<LiveBlock>, QuerierError>> + Send + use<> {
+		stream::unfold(Some(self.0.subscribe()), |receiver| async move {
+			let mut receiver = receiver?;
+
+			match receiver.recv().await {
+				Ok(live_block) => Some((Ok(live_block), Some(receiver))),
+
+				// the subscription ends, as the skipped blocks can not be replayed
+				Err(RecvError::Lagged(skipped)) => Some((Err(QuerierError::Lagged(skipped)), None)),
+				Err(RecvError::Closed) => None,
+			}
+		})
+	}
+}
+
+impl Default for Feed {
+	fn default() -> Self {
+		Self(broadcast::channel(CAPACITY).0)
+	}
+}
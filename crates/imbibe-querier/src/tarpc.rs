@@ -1,3 +1,6 @@
+#[cfg(feature = "server")]
+pub mod metrics;
+
 #[cfg(feature = "server")]
 pub mod server;
 
@@ -7,7 +10,16 @@ pub use self::error::QueryTarpcError;
 
 use core::num::NonZeroU64;
 
-use imbibe_domain::{Sha256, block::Block, tx::Tx};
+use imbibe_domain::{
+	Address, Sha256,
+	block::{Block, TimeBound},
+	evidence::Evidence,
+	filter::TxFilter,
+	page::{EvidenceCursor, HeightRange, Order, Page, TxCursor},
+	tx::Tx,
+	validator::{BlockSigner, ValidatorSet},
+};
+use jiff::Timestamp;
 
 #[tarpc::service]
 pub trait Query {
@@ -15,10 +27,63 @@ pub trait Query {
 
 	async fn block_by_block_hash(block_hash: Sha256) -> Result<Block, QueryTarpcError>;
 
+	async fn block_by_time(time: Timestamp, bound: TimeBound) -> Result<Block, QueryTarpcError>;
+
 	async fn tx_by_block_height_and_tx_idx_in_block(
 		height: NonZeroU64,
 		tx_idx_in_block: u64,
 	) -> Result<Tx, QueryTarpcError>;
 
 	async fn tx_by_tx_hash(tx_hash: Sha256) -> Result<Tx, QueryTarpcError>;
+
+	async fn txs_by_block_height(height: NonZeroU64) -> Result<Vec<Tx>, QueryTarpcError>;
+
+	async fn indexed_height(chain_id: String) -> Result<Option<NonZeroU64>, QueryTarpcError>;
+
+	async fn txs_by_address(
+		address: Address,
+		cursor: Option<TxCursor>,
+		limit: NonZeroU64,
+	) -> Result<Page<Tx, TxCursor>, QueryTarpcError>;
+
+	async fn blocks(
+		range: HeightRange,
+		cursor: Option<NonZeroU64>,
+		order: Order,
+		limit: NonZeroU64,
+	) -> Result<Page<Block, NonZeroU64>, QueryTarpcError>;
+
+	async fn txs(
+		range: HeightRange,
+		cursor: Option<TxCursor>,
+		order: Order,
+		limit: NonZeroU64,
+	) -> Result<Page<Tx, TxCursor>, QueryTarpcError>;
+
+	async fn search_txs(
+		filter: TxFilter,
+		cursor: Option<TxCursor>,
+		order: Order,
+		limit: NonZeroU64,
+	) -> Result<Page<Tx, TxCursor>, QueryTarpcError>;
+
+	async fn validator_set(height: NonZeroU64) -> Result<ValidatorSet, QueryTarpcError>;
+
+	async fn block_signers(height: NonZeroU64) -> Result<Vec<BlockSigner>, QueryTarpcError>;
+
+	async fn missed_blocks(
+		address: Address,
+		range: HeightRange,
+		cursor: Option<NonZeroU64>,
+		order: Order,
+		limit: NonZeroU64,
+	) -> Result<Page<NonZeroU64, NonZeroU64>, QueryTarpcError>;
+
+	async fn evidence(
+		range: HeightRange,
+		validator: Option<Address>,
+		cursor: Option<EvidenceCursor>,
+		order: Order,
+		limit: NonZeroU64,
+	) -> Result<Page<Evidence, EvidenceCursor>, QueryTarpcError>;
 }
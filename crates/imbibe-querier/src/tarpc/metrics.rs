@@ -0,0 +1,44 @@
+use std::time::Instant;
+
+use metrics::{Unit, describe_histogram, histogram};
+use tarpc::{
+	RequestName, ServerError, context,
+	server::request_hook::{AfterRequest, BeforeRequest},
+};
+
+const REQUEST_DURATION: &str = "imbibe_querier_tarpc_request_duration_seconds";
+
+pub fn describe() {
+	describe_histogram!(
+		REQUEST_DURATION,
+		Unit::Seconds,
+		"latency of serving the tarpc requests, by method"
+	);
+}
+
+// times the requests by method, when hooked onto the query server with `before_and_after`
+#[derive(Debug, Clone, Default)]
+pub struct RequestTimer {
+	method: String,
+	started: Option<Instant>,
+}
+
+impl<Req> BeforeRequest<Req> for RequestTimer
+where
+	Req: RequestName,
+{
+	async fn before(&mut self, _: &mut context::Context, req: &Req) -> Result<(), ServerError> {
+		self.method = req.name().to_owned();
+		self.started = Some(Instant::now());
+
+		Ok(())
+	}
+}
+
+impl<Resp> AfterRequest<Resp> for RequestTimer {
+	async fn after(&mut self, _: &mut context::Context, _: &mut Result<Resp, ServerError>) {
+		if let Some(started) = self.started {
+			histogram!(REQUEST_DURATION, "method" => self.method.clone()).record(started.elapsed());
+		}
+	}
+}
@@ -1,7 +1,16 @@
 use core::num::NonZeroU64;
 
 use bon::Builder;
-use imbibe_domain::{Sha256, block::Block, tx::Tx};
+use imbibe_domain::{
+	Address, Sha256,
+	block::{Block, TimeBound},
+	evidence::Evidence,
+	filter::TxFilter,
+	page::{EvidenceCursor, HeightRange, Order, Page, TxCursor},
+	tx::Tx,
+	validator::{BlockSigner, ValidatorSet},
+};
+use jiff::Timestamp;
 
 use crate::server::Querier;
 
@@ -45,6 +54,23 @@ impl Query for QueryServer {
 		.map_err(From::from)
 	}
 
+	async fn block_by_time(
+		self,
+		ctx: tarpc::context::Context,
+		time: Timestamp,
+		bound: TimeBound,
+	) -> Result<Block, QueryTarpcError> {
+		tokio::time::timeout(
+			ctx.deadline.saturating_duration_since(std::time::Instant::now()),
+			self.querier.get_block_by_time(&time, bound),
+		)
+		.await
+		.map_err(super::error::QueryTarpcErrorKind::from)
+		.and_then(|r| r.map_err(From::from))
+		.map_err(From::from)
+		.inspect_err(|e| tracing::error!("{e}"))
+	}
+
 	async fn tx_by_block_height_and_tx_idx_in_block(
 		self,
 		ctx: tarpc::context::Context,
@@ -77,4 +103,183 @@ impl Query for QueryServer {
 		.map_err(From::from)
 		.inspect_err(|e| tracing::error!("{e}"))
 	}
+
+	async fn txs_by_block_height(
+		self,
+		ctx: tarpc::context::Context,
+		height: NonZeroU64,
+	) -> Result<Vec<Tx>, QueryTarpcError> {
+		tokio::time::timeout(
+			ctx.deadline.saturating_duration_since(std::time::Instant::now()),
+			self.querier.get_txs_by_block_height(height),
+		)
+		.await
+		.map_err(super::error::QueryTarpcErrorKind::from)
+		.and_then(|r| r.map_err(From::from))
+		.map_err(From::from)
+		.inspect_err(|e| tracing::error!("{e}"))
+	}
+
+	async fn indexed_height(
+		self,
+		ctx: tarpc::context::Context,
+		chain_id: String,
+	) -> Result<Option<NonZeroU64>, QueryTarpcError> {
+		tokio::time::timeout(
+			ctx.deadline.saturating_duration_since(std::time::Instant::now()),
+			self.querier.get_indexed_height(&chain_id),
+		)
+		.await
+		.map_err(super::error::QueryTarpcErrorKind::from)
+		.and_then(|r| r.map_err(From::from))
+		.map_err(From::from)
+		.inspect_err(|e| tracing::error!("{e}"))
+	}
+
+	async fn txs_by_address(
+		self,
+		ctx: tarpc::context::Context,
+		address: Address,
+		cursor: Option<TxCursor>,
+		limit: NonZeroU64,
+	) -> Result<Page<Tx, TxCursor>, QueryTarpcError> {
+		tokio::time::timeout(
+			ctx.deadline.saturating_duration_since(std::time::Instant::now()),
+			self.querier.get_txs_by_address(&address, cursor, limit),
+		)
+		.await
+		.map_err(super::error::QueryTarpcErrorKind::from)
+		.and_then(|r| r.map_err(From::from))
+		.map_err(From::from)
+		.inspect_err(|e| tracing::error!("{e}"))
+	}
+
+	async fn blocks(
+		self,
+		ctx: tarpc::context::Context,
+		range: HeightRange,
+		cursor: Option<NonZeroU64>,
+		order: Order,
+		limit: NonZeroU64,
+	) -> Result<Page<Block, NonZeroU64>, QueryTarpcError> {
+		tokio::time::timeout(
+			ctx.deadline.saturating_duration_since(std::time::Instant::now()),
+			self.querier.get_blocks(range, cursor, order, limit),
+		)
+		.await
+		.map_err(super::error::QueryTarpcErrorKind::from)
+		.and_then(|r| r.map_err(From::from))
+		.map_err(From::from)
+		.inspect_err(|e| tracing::error!("{e}"))
+	}
+
+	async fn txs(
+		self,
+		ctx: tarpc::context::Context,
+		range: HeightRange,
+		cursor: Option<TxCursor>,
+		order: Order,
+		limit: NonZeroU64,
+	) -> Result<Page<Tx, TxCursor>, QueryTarpcError> {
+		tokio::time::timeout(
+			ctx.deadline.saturating_duration_since(std::time::Instant::now()),
+			self.querier.get_txs(range, cursor, order, limit),
+		)
+		.await
+		.map_err(super::error::QueryTarpcErrorKind::from)
+		.and_then(|r| r.map_err(From::from))
+		.map_err(From::from)
+		.inspect_err(|e| tracing::error!("{e}"))
+	}
+
+	async fn search_txs(
+		self,
+		ctx: tarpc::context::Context,
+		filter: TxFilter,
+		cursor: Option<TxCursor>,
+		order: Order,
+		limit: NonZeroU64,
+	) -> Result<Page<Tx, TxCursor>, QueryTarpcError> {
+		tokio::time::timeout(
+			ctx.deadline.saturating_duration_since(std::time::Instant::now()),
+			self.querier.search_txs(&filter, cursor, order, limit),
+		)
+		.await
+		.map_err(super::error::QueryTarpcErrorKind::from)
+		.and_then(|r| r.map_err(From::from))
+		.map_err(From::from)
+		.inspect_err(|e| tracing::error!("{e}"))
+	}
+
+	async fn validator_set(
+		self,
+		ctx: tarpc::context::Context,
+		height: NonZeroU64,
+	) -> Result<ValidatorSet, QueryTarpcError> {
+		tokio::time::timeout(
+			ctx.deadline.saturating_duration_since(std::time::Instant::now()),
+			self.querier.get_validator_set(height),
+		)
+		.await
+		.map_err(super::error::QueryTarpcErrorKind::from)
+		.and_then(|r| r.map_err(From::from))
+		.map_err(From::from)
+		.inspect_err(|e| tracing::error!("{e}"))
+	}
+
+	async fn block_signers(
+		self,
+		ctx: tarpc::context::Context,
+		height: NonZeroU64,
+	) -> Result<Vec<BlockSigner>, QueryTarpcError> {
+		tokio::time::timeout(
+			ctx.deadline.saturating_duration_since(std::time::Instant::now()),
+			self.querier.get_block_signers(height),
+		)
+		.await
+		.map_err(super::error::QueryTarpcErrorKind::from)
+		.and_then(|r| r.map_err(From::from))
+		.map_err(From::from)
+		.inspect_err(|e| tracing::error!("{e}"))
+	}
+
+	async fn missed_blocks(
+		self,
+		ctx: tarpc::context::Context,
+		address: Address,
+		range: HeightRange,
+		cursor: Option<NonZeroU64>,
+		order: Order,
+		limit: NonZeroU64,
+	) -> Result<Page<NonZeroU64, NonZeroU64>, QueryTarpcError> {
+		tokio::time::timeout(
+			ctx.deadline.saturating_duration_since(std::time::Instant::now()),
+			self.querier.get_missed_blocks(&address, range, cursor, order, limit),
+		)
+		.await
+		.map_err(super::error::QueryTarpcErrorKind::from)
+		.and_then(|r| r.map_err(From::from))
+		.map_err(From::from)
+		.inspect_err(|e| tracing::error!("{e}"))
+	}
+
+	async fn evidence(
+		self,
+		ctx: tarpc::context::Context,
+		range: HeightRange,
+		validator: Option<Address>,
+		cursor: Option<EvidenceCursor>,
+		order: Order,
+		limit: NonZeroU64,
+	) -> Result<Page<Evidence, EvidenceCursor>, QueryTarpcError> {
+		tokio::time::timeout(
+			ctx.deadline.saturating_duration_since(std::time::Instant::now()),
+			self.querier.get_evidence(range, validator.as_ref(), cursor, order, limit),
+		)
+		.await
+		.map_err(super::error::QueryTarpcErrorKind::from)
+		.and_then(|r| r.map_err(From::from))
+		.map_err(From::from)
+		.inspect_err(|e| tracing::error!("{e}"))
+	}
 }
@@ -10,4 +10,8 @@ pub enum TelemetryError {
 
 	#[error("exporter build error: {0}")]
 	ExporterBuild(#[from] ExporterBuildError),
+
+	#[cfg(feature = "metrics")]
+	#[error("metrics build error: {0}")]
+	MetricsBuild(#[from] metrics_exporter_prometheus::BuildError),
 }
@@ -14,6 +14,15 @@ use tracing_subscriber::{EnvFilter, Registry, fmt::format::FmtSpan, layer::Subsc
 
 use self::error::Result;
 
+#[cfg(feature = "metrics")]
+pub use metrics_exporter_prometheus::PrometheusHandle;
+
+// buckets, in seconds, of the latency histograms, from a millisecond upto a minute
+#[cfg(feature = "metrics")]
+const LATENCY_BUCKETS: &[f64] = &[
+	0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1., 2.5, 5., 10., 30., 60.,
+];
+
 pub fn init_subscriber<S>(s: S) -> Result<()>
 where
 	S: Subscriber + Send + Sync,
@@ -53,3 +62,13 @@ where
 
 	Ok(subscriber)
 }
+
+// installs the global recorder of the metrics, whose handle renders them in the prometheus text
+// format, and whose upkeep must be run periodically
+#[cfg(feature = "metrics")]
+pub fn install_metrics_recorder() -> Result<PrometheusHandle> {
+	metrics_exporter_prometheus::PrometheusBuilder::new()
+		.set_buckets(LATENCY_BUCKETS)?
+		.install_recorder()
+		.map_err(From::from)
+}
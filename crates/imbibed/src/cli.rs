@@ -0,0 +1,60 @@
+use std::path::PathBuf;
+
+use clap::{Parser, Subcommand};
+
+#[derive(Parser)]
+#[command(about = "a cosmos chain indexer", long_about = None)]
+#[command(version)]
+pub struct Cli {
+	/// is the path to a RON, TOML or YAML config file, overriding the base config and overridden by the
+	/// environment variables.
+	#[arg(global = true, long)]
+	pub config: Option<PathBuf>,
+
+	/// is the role to run the process in. Without one, every role enabled by the features is run.
+	#[command(subcommand)]
+	pub command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+	/// inspects the config.
+	Config {
+		#[command(subcommand)]
+		command: ConfigCommand,
+	},
+
+	/// indexes the live blocks, backfilling the blocks missed since the indexed height.
+	#[cfg(feature = "indexer")]
+	Index,
+
+	/// indexes the missing blocks within a height range, and exits.
+	#[cfg(feature = "indexer")]
+	Backfill {
+		/// is the height of the first block to backfill. Must be a positive integer.
+		#[arg(long)]
+		from: core::num::NonZeroU64,
+
+		/// is the height of the last block to backfill. Must be a positive integer.
+		#[arg(long)]
+		to: core::num::NonZeroU64,
+	},
+
+	/// serves the queries over the querier servers enabled by the features.
+	#[cfg(feature = "querier")]
+	Serve,
+
+	/// applies the pending migrations to the database, and exits.
+	#[cfg(feature = "persistence")]
+	Migrate,
+
+	/// verifies that the database schema matches the migrations of this binary, and exits.
+	#[cfg(feature = "persistence")]
+	Verify,
+}
+
+#[derive(Subcommand)]
+pub enum ConfigCommand {
+	/// prints the effective config, with the passwords of the urls redacted, and exits.
+	Show,
+}
@@ -0,0 +1,56 @@
+use core::{num::NonZeroU64, time::Duration};
+
+use futures::{StreamExt, stream};
+use imbibe_persistence::store;
+use imbibe_querier::server::Querier;
+use tokio::sync::broadcast::{self, error::RecvError};
+
+// number of the indexed block heights buffered for the feed
+pub const INDEXED_BLOCK_CAPACITY: usize = 64;
+
+const LISTEN_BACKOFF: Duration = Duration::from_secs(1);
+
+// feeds the live blocks to the subscribers of the queriers, following the indexer of this process
+// if any, or else the indexer of another process over postgres notifications
+pub async fn run(
+	querier: Querier,
+	indexed_block_receiver: Option<broadcast::Receiver<NonZeroU64>>,
+	db_url: String,
+) -> anyhow::Result<()> {
+	if let Some(receiver) = indexed_block_receiver {
+		let heights = stream::unfold(receiver, async |mut receiver| {
+			loop {
+				match receiver.recv().await {
+					Ok(height) => return Some((height, receiver)),
+					Err(RecvError::Lagged(skipped)) => {
+						tracing::warn!("feed skipped {skipped} indexed blocks")
+					},
+					Err(RecvError::Closed) => return None,
+				}
+			}
+		});
+
+		querier.feed(heights).await;
+
+		return Ok(());
+	}
+
+	loop {
+		match store::listen_indexed_blocks(&db_url).await {
+			Ok(heights) => {
+				tracing::info!("listening for indexed blocks");
+
+				let heights = heights.filter_map(async |height| {
+					height.inspect_err(|e| tracing::warn!("indexed block error: {e}")).ok()
+				});
+
+				querier.feed(heights).await;
+
+				tracing::warn!("stopped listening for indexed blocks, relistening");
+			},
+			Err(e) => tracing::warn!("failed to listen for indexed blocks: {e}, retrying"),
+		}
+
+		tokio::time::sleep(LISTEN_BACKOFF).await;
+	}
+}
@@ -0,0 +1,24 @@
+use imbibe_querier::{
+	grpc::server::{QueryGrpcServer, QueryServer},
+	server::Querier,
+};
+use tokio::net::{ToSocketAddrs, lookup_host};
+use tonic::transport::Server;
+
+pub async fn run<A>(querier: Querier, sock_addr: A) -> anyhow::Result<()>
+where
+	A: ToSocketAddrs,
+{
+	let sock_addr = lookup_host(sock_addr)
+		.await?
+		.next()
+		.ok_or_else(|| anyhow::anyhow!("grpc listening address must resolve"))?;
+
+	tracing::info!("querier grpc listening port {}", sock_addr.port());
+
+	let server = QueryGrpcServer::builder().querier(querier).build();
+
+	Server::builder().add_service(QueryServer::new(server)).serve(sock_addr).await?;
+
+	Ok(())
+}
@@ -0,0 +1,23 @@
+use imbibe_querier::{http, server::Querier};
+use tokio::net::{TcpListener, ToSocketAddrs};
+
+pub async fn run<A>(querier: Querier, sock_addr: A) -> anyhow::Result<()>
+where
+	A: ToSocketAddrs,
+{
+	let listener = TcpListener::bind(sock_addr).await?;
+
+	tracing::info!(
+		"querier http listening port {}",
+		listener.local_addr()?.port()
+	);
+
+	let router = http::router(querier.clone());
+
+	#[cfg(feature = "graphql-querier")]
+	let router = router.merge(imbibe_querier::graphql::router(querier));
+
+	axum::serve(listener, router).await?;
+
+	Ok(())
+}
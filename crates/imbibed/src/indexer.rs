@@ -1,51 +1,147 @@
-use core::num::{NonZeroU64, NonZeroUsize};
+use core::{num::NonZeroU64, time::Duration};
 
-use imbibe_indexer::{BackfillIndexer, LiveIndexer, WebSocketClient};
-use imbibe_persistence::pool::DbPool;
-use tokio::sync::oneshot;
+use imbibe_indexer::{
+	BackfillIndexer, Client, ForkPolicy, LiveIndexer, RetryPolicy, SubscriptionClient,
+	WebSocketClient,
+};
+use imbibe_persistence::{pool::DbPool, store};
+use tokio::sync::{broadcast, oneshot};
 
-pub async fn run<U>(
-	url: U,
+use crate::config::{IndexerConfig, OnFork, StartHeight};
+
+pub async fn run(
 	pool: DbPool,
-	batch: NonZeroUsize,
-	workers: NonZeroUsize,
-) -> anyhow::Result<()>
-where
-	U: AsRef<str>,
-{
-	let (client, driver) = WebSocketClient::new(url.as_ref()).await?;
+	config: IndexerConfig,
+	indexed_block_transmitter: Option<broadcast::Sender<NonZeroU64>>,
+) -> anyhow::Result<()> {
+	let retry_policy = make_retry_policy(&config);
+
+	let IndexerConfig {
+		tm_ws_url: url,
+		start_height,
+		batch,
+		workers,
+		min_reconnect_backoff_millis,
+		max_reconnect_backoff_millis,
+		on_fork,
+		..
+	} = config;
+
+	let (client, driver) = WebSocketClient::new(url.as_str()).await?;
 	let driver_handle = tokio::spawn(driver.run());
 
+	let status = client.status().await?;
+	let chain_id = status.node_info.network.to_string();
+	let start_height = match start_height {
+		StartHeight::Earliest => NonZeroU64::new(status.sync_info.earliest_block_height.value())
+			.unwrap_or(NonZeroU64::MIN),
+		StartHeight::Height(height) => height,
+	};
+
+	tracing::info!("indexing chain {chain_id} from block {start_height}");
+
+	let indexed_height = {
+		let mut conn = pool.get().await?;
+		if let Some(stored) = store::fetch_start_height(&mut conn, &chain_id).await?
+			&& start_height < stored
+		{
+			tracing::warn!(
+				"ignoring start height {start_height} as chain {chain_id} is already indexed from block {stored}, which can only be raised"
+			);
+		}
+
+		store::raise_start_height(&mut conn, &chain_id, start_height).await?
+	};
+
+	tracing::info!("chain {chain_id} is indexed upto block {indexed_height}");
+
 	let (tx, rx) = oneshot::channel();
 
 	let live_indexer = LiveIndexer::builder()
 		.pool(pool.clone())
-		.client(client.clone())
+		.tm_ws_url(url.as_str())
+		.batch(batch)
+		.workers(workers)
+		.min_reconnect_backoff(Duration::from_millis(min_reconnect_backoff_millis))
+		.max_reconnect_backoff(Duration::from_millis(max_reconnect_backoff_millis))
+		.retry_policy(retry_policy)
+		.fork_policy(match on_fork {
+			OnFork::Halt => ForkPolicy::Halt,
+			OnFork::Rollback => ForkPolicy::Rollback,
+		})
 		.first_block_transmitter(tx)
-		.build();
+		.maybe_indexed_block_transmitter(indexed_block_transmitter)
+		.build()?;
 
 	let live_indexer_handle = tokio::spawn(live_indexer.start());
 
+	let lo = indexed_height
+		.checked_add(1)
+		.and_then(NonZeroU64::new)
+		.expect("indexed height must be less than u64::MAX");
+
 	if let Ok(Some(hi)) = rx
 		.await
 		.inspect(|h| tracing::info!("first live block height received: {h}"))
 		.map(|h| h.get().checked_sub(1).expect("must not underflow due to positive height"))
 		.map(NonZeroU64::new)
+		&& lo <= hi
 	{
 		BackfillIndexer::builder()
 			.pool(pool)
-			.client(client)
+			.client(client.clone())
 			.batch(batch)
 			.workers(workers)
-			.lo(NonZeroU64::MIN)
+			.lo(lo)
 			.hi(hi)
+			.retry_policy(retry_policy)
 			.build()
 			.map(|indexer| tokio::spawn(indexer.start()))?
 			.await??;
 	}
 
-	live_indexer_handle.await??;
+	client.close()?;
 	driver_handle.await??;
 
+	live_indexer_handle.await??;
+
 	Ok(())
 }
+
+// backfills the missing blocks from `from` upto `to`, without following the live blocks
+pub async fn backfill(
+	pool: DbPool,
+	config: IndexerConfig,
+	from: NonZeroU64,
+	to: NonZeroU64,
+) -> anyhow::Result<()> {
+	let retry_policy = make_retry_policy(&config);
+
+	let (client, driver) = WebSocketClient::new(config.tm_ws_url.as_str()).await?;
+	let driver_handle = tokio::spawn(driver.run());
+
+	let backfilled = BackfillIndexer::builder()
+		.pool(pool)
+		.client(client.clone())
+		.batch(config.batch)
+		.workers(config.workers)
+		.lo(from)
+		.hi(to)
+		.retry_policy(retry_policy)
+		.build()
+		.map(|indexer| tokio::spawn(indexer.start()))?
+		.await?;
+
+	client.close()?;
+	driver_handle.await??;
+
+	backfilled.map_err(From::from)
+}
+
+fn make_retry_policy(config: &IndexerConfig) -> RetryPolicy {
+	RetryPolicy::builder()
+		.max_attempts(config.max_retry_attempts)
+		.min_backoff(Duration::from_millis(config.min_retry_backoff_millis))
+		.max_backoff(Duration::from_millis(config.max_retry_backoff_millis))
+		.build()
+}
@@ -1,7 +1,23 @@
+pub mod cli;
 pub mod config;
 
+#[cfg(feature = "querier")]
+pub mod feed;
+
+#[cfg(feature = "grpc-querier")]
+pub mod grpc_querier;
+
+#[cfg(feature = "http-querier")]
+pub mod http_querier;
+
 #[cfg(feature = "indexer")]
 pub mod indexer;
 
+#[cfg(feature = "persistence")]
+pub mod migration;
+
+#[cfg(feature = "metrics")]
+pub mod metrics;
+
 #[cfg(feature = "tarpc-querier")]
 pub mod tarpc_querier;
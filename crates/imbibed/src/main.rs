@@ -1,8 +1,22 @@
-use imbibe::config;
+use clap::Parser;
+use imbibe::{
+	cli::{Cli, Command, ConfigCommand},
+	config,
+};
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-	let config = tokio::task::spawn_blocking(config::get_configuration).await??;
+	let cli = Cli::parse();
+
+	let config_path = cli.config;
+	let config =
+		tokio::task::spawn_blocking(move || config::get_configuration(config_path.as_deref()))
+			.await??;
+
+	if let Some(Command::Config { command: ConfigCommand::Show }) = cli.command {
+		println!("{}", config.show()?);
+		return Ok(());
+	}
 
 	#[cfg(not(feature = "disable-telemetry"))]
 	imbibe_telemetry::make_tracing_subscriber(
@@ -15,32 +29,138 @@ async fn main() -> anyhow::Result<()> {
 	.and_then(imbibe_telemetry::init_subscriber)?;
 
 	#[cfg(feature = "persistence")]
-	let pool = imbibe_persistence::pool::establish_pool(config.db.db_url, config.db.max_conn).await?;
+	{
+		use imbibe::config::Migrations;
+
+		let (migrations, exit) = match cli.command {
+			Some(Command::Migrate) => (Migrations::Apply, true),
+			Some(Command::Verify) => (Migrations::Verify, true),
+			_ => (config.db.migrations, false),
+		};
+
+		imbibe::migration::run(config.db.db_url.clone(), migrations).await?;
+
+		if exit {
+			return Ok(());
+		}
+	}
+
+	#[cfg(feature = "metrics")]
+	let metrics_handle = imbibe::metrics::install()?;
+
+	#[cfg(feature = "persistence")]
+	let pool = imbibe_persistence::pool::establish_pool(config.db.db_url.clone(), config.db.max_conn)
+		.await?;
+
+	#[cfg(feature = "metrics")]
+	let metrics_server_handle = {
+		let metrics_server =
+			imbibe::metrics::run(metrics_handle, pool.clone(), config.metrics.listen);
+
+		tokio::spawn(metrics_server)
+	};
 
 	#[cfg(feature = "indexer")]
-	let indexer_handle = {
-		let indexer = imbibe::indexer::run(
-			config.indexer.tm_ws_url,
-			pool.clone(),
-			config.indexer.batch,
-			config.indexer.workers,
-		);
+	if let Some(Command::Backfill { from, to }) = cli.command {
+		return imbibe::indexer::backfill(pool, config.indexer, from, to).await;
+	}
 
-		tokio::spawn(indexer)
+	// without a subcommand, every role enabled by the features is run
+	#[cfg(feature = "indexer")]
+	let index = matches!(cli.command, None | Some(Command::Index));
+
+	#[cfg(feature = "querier")]
+	let serve = matches!(cli.command, None | Some(Command::Serve));
+
+	#[cfg(feature = "querier")]
+	let querier = imbibe_querier::server::Querier::builder()
+		.pool(pool.clone())
+		.maybe_bech32_prefixes(
+			config
+				.querier
+				.bech32_prefix
+				.as_deref()
+				.map(imbibe_domain::Bech32Prefixes::from_account_prefix),
+		)
+		.build();
+
+	// the queriers follow the indexer of this process if any, or else of another process
+	#[cfg(all(feature = "indexer", feature = "querier"))]
+	let (indexed_block_transmitter, indexed_block_receiver) = if index && serve {
+		let (tx, rx) = tokio::sync::broadcast::channel(imbibe::feed::INDEXED_BLOCK_CAPACITY);
+		(Some(tx), Some(rx))
+	} else {
+		(None, None)
 	};
 
+	#[cfg(all(feature = "indexer", not(feature = "querier")))]
+	let indexed_block_transmitter = None;
+
+	#[cfg(all(feature = "querier", not(feature = "indexer")))]
+	let indexed_block_receiver = None;
+
+	#[cfg(feature = "indexer")]
+	let indexer_handle = index.then(|| {
+		let indexer = imbibe::indexer::run(pool.clone(), config.indexer, indexed_block_transmitter);
+
+		tokio::spawn(indexer)
+	});
+
+	#[cfg(feature = "querier")]
+	let feed_handle = serve.then(|| {
+		let feed = imbibe::feed::run(querier.clone(), indexed_block_receiver, config.db.db_url);
+
+		tokio::spawn(feed)
+	});
+
 	#[cfg(feature = "tarpc-querier")]
-	let tarpc_querier_handle = {
-		let tarpc_querier = imbibe::tarpc_querier::run(pool, config.querier.listen);
+	let tarpc_querier_handle = serve.then(|| {
+		let tarpc_querier = imbibe::tarpc_querier::run(querier.clone(), config.querier.listen);
 
 		tokio::spawn(tarpc_querier)
-	};
+	});
+
+	#[cfg(feature = "http-querier")]
+	let http_querier_handle = serve.then(|| {
+		let http_querier = imbibe::http_querier::run(querier.clone(), config.http_querier.listen);
+
+		tokio::spawn(http_querier)
+	});
+
+	#[cfg(feature = "grpc-querier")]
+	let grpc_querier_handle = serve.then(|| {
+		let grpc_querier = imbibe::grpc_querier::run(querier.clone(), config.grpc_querier.listen);
+
+		tokio::spawn(grpc_querier)
+	});
 
 	#[cfg(feature = "indexer")]
-	indexer_handle.await??;
+	if let Some(handle) = indexer_handle {
+		handle.await??;
+	}
 
 	#[cfg(feature = "tarpc-querier")]
-	tarpc_querier_handle.await??;
+	if let Some(handle) = tarpc_querier_handle {
+		handle.await??;
+	}
+
+	#[cfg(feature = "http-querier")]
+	if let Some(handle) = http_querier_handle {
+		handle.await??;
+	}
+
+	#[cfg(feature = "grpc-querier")]
+	if let Some(handle) = grpc_querier_handle {
+		handle.await??;
+	}
+
+	#[cfg(feature = "querier")]
+	if let Some(handle) = feed_handle {
+		handle.await??;
+	}
+
+	#[cfg(feature = "metrics")]
+	metrics_server_handle.await??;
 
 	Ok(())
 }
@@ -0,0 +1,56 @@
+use core::{future, time::Duration};
+
+use axum::{Router, routing::get};
+use imbibe_persistence::pool::DbPool;
+use imbibe_telemetry::PrometheusHandle;
+use tokio::net::{TcpListener, ToSocketAddrs};
+
+// interval of running the upkeep of the recorder, which drains the histograms between scrapes
+const UPKEEP_INTERVAL: Duration = Duration::from_secs(5);
+
+// installs the recorder before any metric is emitted, so that none of them is lost
+pub fn install() -> anyhow::Result<PrometheusHandle> {
+	let handle = imbibe_telemetry::install_metrics_recorder()?;
+
+	imbibe_persistence::metrics::describe();
+
+	#[cfg(feature = "indexer")]
+	imbibe_indexer::metrics::describe();
+
+	#[cfg(feature = "tarpc-querier")]
+	imbibe_querier::tarpc::metrics::describe();
+
+	Ok(handle)
+}
+
+pub async fn run<A>(handle: PrometheusHandle, pool: DbPool, sock_addr: A) -> anyhow::Result<()>
+where
+	A: ToSocketAddrs,
+{
+	let listener = TcpListener::bind(sock_addr).await?;
+
+	tracing::info!("metrics listening port {}", listener.local_addr()?.port());
+
+	let upkeep_handle = handle.clone();
+	tokio::spawn(async move {
+		let mut interval = tokio::time::interval(UPKEEP_INTERVAL);
+
+		loop {
+			interval.tick().await;
+			upkeep_handle.run_upkeep();
+		}
+	});
+
+	// the pool status is sampled on scrape, being a gauge of the moment
+	let router = Router::new().route(
+		"/metrics",
+		get(move || {
+			imbibe_persistence::metrics::record_pool_status(&pool);
+			future::ready(handle.render())
+		}),
+	);
+
+	axum::serve(listener, router).await?;
+
+	Ok(())
+}
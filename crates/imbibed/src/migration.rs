@@ -0,0 +1,29 @@
+use imbibe_persistence::migration;
+
+use crate::config::Migrations;
+
+// applies or verifies the embedded migrations against the database, refusing to start against a
+// database schema newer than this binary
+pub async fn run(db_url: String, migrations: Migrations) -> anyhow::Result<()> {
+	match migrations {
+		Migrations::Off => {},
+		Migrations::Apply => {
+			let applied =
+				tokio::task::spawn_blocking(move || migration::run_pending_migrations(&db_url))
+					.await??;
+
+			if applied.is_empty() {
+				tracing::info!("database schema is up to date");
+			} else {
+				tracing::info!("applied migrations {}", applied.join(", "));
+			}
+		},
+		Migrations::Verify => {
+			tokio::task::spawn_blocking(move || migration::verify_migrations(&db_url)).await??;
+
+			tracing::info!("verified database schema");
+		},
+	}
+
+	Ok(())
+}
@@ -1,18 +1,19 @@
 use core::future;
 
 use futures::StreamExt;
-use imbibe_persistence::pool::DbPool;
 use imbibe_querier::{
 	server::Querier,
 	tarpc::{Query, server::QueryServer},
 };
+#[cfg(feature = "metrics")]
+use tarpc::server::request_hook::RequestHook;
 use tarpc::{
 	server::{BaseChannel, Channel, incoming::Incoming},
 	tokio_serde::formats::Json,
 };
 use tokio::net::ToSocketAddrs;
 
-pub async fn run<A>(pool: DbPool, sock_addr: A) -> anyhow::Result<()>
+pub async fn run<A>(querier: Querier, sock_addr: A) -> anyhow::Result<()>
 where
 	A: ToSocketAddrs,
 {
@@ -23,14 +24,22 @@ where
 		listener.local_addr().port()
 	);
 
-	let querier = Querier::builder().pool(pool).build();
 	let rpc = listener
 		.filter_map(|r| future::ready(r.ok()))
 		.map(BaseChannel::with_defaults)
 		.max_channels_per_key(1, |t| t.transport().peer_addr().unwrap().ip())
 		.map(move |channel| {
 			let server = QueryServer::builder().querier(querier.clone()).build();
-			channel.execute(server.serve()).for_each(async |r| {
+
+			#[cfg(feature = "metrics")]
+			let serve = server
+				.serve()
+				.before_and_after(imbibe_querier::tarpc::metrics::RequestTimer::default());
+
+			#[cfg(not(feature = "metrics"))]
+			let serve = server.serve();
+
+			channel.execute(serve).for_each(async |r| {
 				tokio::spawn(r);
 			})
 		})